@@ -1,10 +1,28 @@
 //! This module contains functionality for working with codes and sets of codes.
 
-use std::fmt::Debug;
-use std::num::NonZeroU128;
+use alloc::{format, string::String};
+use core::fmt::Debug;
+use core::num::NonZeroU128;
 
 use thiserror::Error;
 
+use crate::verifier::VerifierOption;
+
+/// The number of distinct codes. Every code has an index below this number,
+/// see [`Code::to_index`], so it can be used to size lookup tables.
+///
+/// ```rust
+/// use turing_machine_ai::code::{Code, Set, CODE_COUNT};
+///
+/// let mut digit_sums = [0; CODE_COUNT];
+/// for (index, code) in Set::all().iter_with_index() {
+///     digit_sums[index] = code.digit_sum();
+/// }
+/// assert_eq!(digit_sums[Code::from_digits(2, 4, 1)?.to_index()], 7);
+/// # Ok::<(), turing_machine_ai::code::Error>(())
+/// ```
+pub const CODE_COUNT: usize = 125;
+
 /// A Turing Machine code, represented by a flipped bit in a [`u128`]. This is
 /// the most efficient format for use with [`Set`] since it allows for fast
 /// set inclusion checks.
@@ -33,9 +51,25 @@ pub enum SequenceOrder {
     NoOrder,
 }
 
+/// One of the three positions of a code. In the game, each is marked by both
+/// a shape and a colour: a blue triangle, a yellow square and a purple circle.
+///
+/// The discriminants match the order of [`Code::digits_array`].
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub enum Color {
+    /// The blue triangle (△).
+    Triangle = 0,
+    /// The yellow square (□).
+    Square = 1,
+    /// The purple circle (○).
+    Circle = 2,
+}
+
 impl Code {
-    fn digits_to_index(triangle: u8, square: u8, circle: u8) -> usize {
-        (usize::from(triangle) - 1) + (usize::from(square) - 1) * 5 + (usize::from(circle) - 1) * 25
+    const fn digits_to_index(triangle: u8, square: u8, circle: u8) -> usize {
+        // `usize::from` is not available in a `const fn`, but the casts are
+        // lossless.
+        (triangle as usize - 1) + (square as usize - 1) * 5 + (circle as usize - 1) * 25
     }
 
     /// Get the code with the given digits.
@@ -50,24 +84,127 @@ impl Code {
     /// assert!(code::Code::from_digits(1, 2, 3).is_ok());
     /// assert_eq!(code::Code::from_digits(3, 4, 9), Err(code::Error::InvalidDigits));
     /// ```
+    ///
+    /// Since this is a `const fn`, it can be used to define constants.
+    /// ```rust
+    /// use turing_machine_ai::code::Code;
+    /// const SOLUTION: Code = match Code::from_digits(2, 4, 1) {
+    ///     Ok(code) => code,
+    ///     Err(_) => panic!("invalid code"),
+    /// };
+    /// assert_eq!(SOLUTION.digits(), (2, 4, 1));
+    /// ```
     // We conclude that this function cannot actually panic, but test this as
     // well through the proptest in the `::tests` module.
     #[allow(clippy::missing_panics_doc)]
-    pub fn from_digits(triangle: u8, square: u8, circle: u8) -> Result<Self, Error> {
-        if !(1..=5).contains(&triangle) || !(1..=5).contains(&square) || !(1..=5).contains(&circle)
-        {
+    pub const fn from_digits(triangle: u8, square: u8, circle: u8) -> Result<Self, Error> {
+        const fn is_valid(digit: u8) -> bool {
+            1 <= digit && digit <= 5
+        }
+        if !is_valid(triangle) || !is_valid(square) || !is_valid(circle) {
             Err(Error::InvalidDigits)
         } else {
-            Ok(Code {
-                bits: (1 << Self::digits_to_index(triangle, square, circle))
-                    .try_into()
-                    // We have checked that the index is between 0-124 incl.
-                    // and so this can never fail.
-                    .unwrap(),
-            })
+            match NonZeroU128::new(1 << Self::digits_to_index(triangle, square, circle)) {
+                Some(bits) => Ok(Code { bits }),
+                // We have checked that the index is between 0-124 incl. and
+                // so this can never happen.
+                None => unreachable!(),
+            }
         }
     }
 
+    /// Get the code with the given digits, without checking that they are
+    /// valid. This avoids the range checks of [`Code::from_digits`] for
+    /// digits that are known to be valid.
+    ///
+    /// # Safety
+    /// Each of the digits must lie in the range `1..=5`.
+    #[must_use]
+    pub const unsafe fn from_digits_unchecked(triangle: u8, square: u8, circle: u8) -> Self {
+        Code {
+            // SAFETY: The caller guarantees that the digits are valid, so the
+            // index lies between 0-124 incl. and the bit is never zero.
+            bits: unsafe {
+                NonZeroU128::new_unchecked(1 << Self::digits_to_index(triangle, square, circle))
+            },
+        }
+    }
+
+    /// Get the index of this code, between 0 and 124 inclusive. The index is
+    /// `(triangle - 1) + 5 * (square - 1) + 25 * (circle - 1)`, so the
+    /// triangle digit varies fastest and the circle digit slowest. This is
+    /// also the order in which a [`Set`] is iterated. The layout is
+    /// guaranteed not to change, so the index can be used to address tables
+    /// of size [`CODE_COUNT`]. See [`Code::from_index`] for the inverse.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::Code;
+    /// assert_eq!(Code::from_digits(1, 1, 1)?.to_index(), 0);
+    /// assert_eq!(Code::from_digits(2, 3, 1)?.to_index(), 11);
+    /// assert_eq!(Code::from_digits(5, 5, 5)?.to_index(), 124);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn to_index(self) -> usize {
+        self.bits.trailing_zeros() as usize
+    }
+
+    /// Get the code with the given index, see [`Code::to_index`]. If the
+    /// index is not below [`CODE_COUNT`], `None` is returned.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::{Code, CODE_COUNT};
+    /// assert_eq!(Code::from_index(11), Some(Code::from_digits(2, 3, 1)?));
+    /// assert_eq!(Code::from_index(CODE_COUNT), None);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub const fn from_index(index: usize) -> Option<Code> {
+        if index >= CODE_COUNT {
+            return None;
+        }
+        match NonZeroU128::new(1 << index) {
+            Some(bits) => Some(Code { bits }),
+            // The index is below 125, so the bit is never zero.
+            None => unreachable!(),
+        }
+    }
+
+    /// Get the code with the given numbers, as printed on the punch cards of
+    /// the game. This is the same as [`Code::from_digits`].
+    ///
+    /// # Errors
+    /// If the provided numbers do not lie in the range `1..=5`, the error
+    /// [`Error::InvalidDigits`] will be returned.
+    ///
+    /// # Example
+    /// ```rust
+    /// use turing_machine_ai::code::{Code, Color};
+    /// let code = Code::from_numbers(2, 4, 1)?;
+    /// assert_eq!(code, Code::from_digits(2, 4, 1)?);
+    /// assert_eq!(code.number(Color::Square), 4);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    pub const fn from_numbers(triangle: u8, square: u8, circle: u8) -> Result<Self, Error> {
+        Code::from_digits(triangle, square, circle)
+    }
+
+    /// Get the number of this code for the given colour.
+    ///
+    /// # Example
+    /// ```rust
+    /// use turing_machine_ai::code::{Code, Color};
+    /// let code = Code::from_digits(3, 5, 1)?;
+    /// assert_eq!(code.number(Color::Triangle), 3);
+    /// assert_eq!(code.number(Color::Square), 5);
+    /// assert_eq!(code.number(Color::Circle), 1);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn number(self, color: Color) -> u8 {
+        self.digits_array()[color as usize]
+    }
+
     /// Get the digits of this code.
     /// ```rust
     /// use turing_machine_ai::code::Code;
@@ -95,6 +232,20 @@ impl Code {
         )
     }
 
+    /// Get the digits of this code as an array, in the order triangle,
+    /// square, circle.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::Code;
+    /// assert_eq!(Code::from_digits(3, 5, 1)?.digits_array(), [3, 5, 1]);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn digits_array(self) -> [u8; 3] {
+        let (triangle, square, circle) = self.digits();
+        [triangle, square, circle]
+    }
+
     /// Returns the digit for the triangle symbol in this code.
     #[must_use]
     pub fn triangle(self) -> u8 {
@@ -161,6 +312,25 @@ impl Code {
             + usize::from(self.circle() % 2 == 0)
     }
 
+    /// Count the digits `d` with `low <= d <= high`.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::code::Code;
+    ///
+    /// assert_eq!(Code::from_digits(1, 4, 5)?.count_in_range(4, 5), 2);
+    /// assert_eq!(Code::from_digits(1, 4, 5)?.count_in_range(2, 3), 0);
+    /// assert_eq!(Code::from_digits(1, 4, 5)?.count_in_range(1, 5), 3);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn count_in_range(self, low: u8, high: u8) -> usize {
+        self.digits_array()
+            .into_iter()
+            .filter(|digit| (low..=high).contains(digit))
+            .count()
+    }
+
     /// Number of digits in ascending or descending order as specified by
     /// verifier 25.
     #[must_use]
@@ -235,15 +405,191 @@ impl Code {
             SequenceOrder::NoOrder
         }
     }
+
+    /// Count the positions in which the digits of this code differ from
+    /// those of the other code.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::Code;
+    /// let code = Code::from_digits(1, 2, 3)?;
+    /// assert_eq!(code.differing_positions(Code::from_digits(1, 2, 4)?), 1);
+    /// assert_eq!(code.differing_positions(Code::from_digits(3, 2, 1)?), 2);
+    /// assert_eq!(code.differing_positions(code), 0);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn differing_positions(self, other: Code) -> usize {
+        let (triangle, square, circle) = self.digits();
+        let (other_triangle, other_square, other_circle) = other.digits();
+        usize::from(triangle != other_triangle)
+            + usize::from(square != other_square)
+            + usize::from(circle != other_circle)
+    }
+
+    /// Iterate over all codes that differ from this code in exactly one
+    /// position.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::Code;
+    /// let code = Code::from_digits(1, 2, 3)?;
+    /// assert_eq!(code.neighbors().count(), 12);
+    /// assert!(code.neighbors().any(|neighbor| neighbor == Code::from_digits(1, 5, 3).unwrap()));
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    pub fn neighbors(self) -> impl Iterator<Item = Code> {
+        Set::all()
+            .into_iter()
+            .filter(move |code| self.differing_positions(*code) == 1)
+    }
+
+    /// Get the digits of this code in ascending order.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::Code;
+    /// assert_eq!(Code::from_digits(3, 1, 5)?.sorted_digits(), [1, 3, 5]);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn sorted_digits(self) -> [u8; 3] {
+        let mut digits: [u8; 3] = self.into();
+        digits.sort_unstable();
+        digits
+    }
+
+    /// Returns whether both codes consist of the same digits, regardless of
+    /// their order.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::Code;
+    /// let code = Code::from_digits(3, 5, 1)?;
+    /// assert!(code.same_multiset(Code::from_digits(1, 5, 3)?));
+    /// assert!(!code.same_multiset(Code::from_digits(1, 5, 5)?));
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn same_multiset(self, other: Code) -> bool {
+        self.sorted_digits() == other.sorted_digits()
+    }
+
+    /// Get the difference between the largest and the smallest digit.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::Code;
+    /// assert_eq!(Code::from_digits(1, 3, 5)?.digit_range(), 4);
+    /// assert_eq!(Code::from_digits(4, 2, 4)?.digit_range(), 2);
+    /// assert_eq!(Code::from_digits(2, 2, 2)?.digit_range(), 0);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn digit_range(self) -> u8 {
+        let [smallest, _, largest] = self.sorted_digits();
+        largest - smallest
+    }
+
+    /// Get the number of distinct digits in the code, which is 1, 2 or 3.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::Code;
+    /// assert_eq!(Code::from_digits(1, 3, 5)?.distinct_digit_count(), 3);
+    /// assert_eq!(Code::from_digits(4, 2, 4)?.distinct_digit_count(), 2);
+    /// assert_eq!(Code::from_digits(2, 2, 2)?.distinct_digit_count(), 1);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn distinct_digit_count(self) -> usize {
+        let [first, second, third] = self.sorted_digits();
+        1 + usize::from(first != second) + usize::from(second != third)
+    }
+
+    /// Returns whether the code reads the same in both directions, i.e.
+    /// whether the triangle digit equals the circle digit.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::Code;
+    /// assert!(Code::from_digits(3, 5, 3)?.is_palindrome());
+    /// assert!(!Code::from_digits(3, 5, 1)?.is_palindrome());
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn is_palindrome(self) -> bool {
+        self.triangle() == self.circle()
+    }
+
+    /// Get the code with the digits of two colours exchanged.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::{Code, Color};
+    /// let code = Code::from_digits(1, 2, 3)?;
+    /// assert_eq!(code.swap(Color::Triangle, Color::Circle), Code::from_digits(3, 2, 1)?);
+    /// assert_eq!(code.swap(Color::Square, Color::Square), code);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn swap(self, a: Color, b: Color) -> Code {
+        let mut digits = self.digits_array();
+        digits.swap(a as usize, b as usize);
+        let [triangle, square, circle] = digits;
+        // SAFETY: The digits are those of a valid code, in a different order.
+        unsafe { Code::from_digits_unchecked(triangle, square, circle) }
+    }
+
+    /// Get the code with every digit moved to the next colour: the triangle
+    /// digit moves to the square, the square digit to the circle, and the
+    /// circle digit to the triangle. Rotating three times gives back the
+    /// original code.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::Code;
+    /// let code = Code::from_digits(1, 2, 3)?;
+    /// assert_eq!(code.rotate(), Code::from_digits(3, 1, 2)?);
+    /// assert_eq!(code.rotate().rotate().rotate(), code);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn rotate(self) -> Code {
+        let (triangle, square, circle) = self.digits();
+        // SAFETY: The digits are those of a valid code, in a different order.
+        unsafe { Code::from_digits_unchecked(circle, triangle, square) }
+    }
 }
 
 impl Debug for Code {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let (triangle, circle, square) = self.digits();
         write!(f, "△: {triangle}, □: {square}, ○: {circle}")
     }
 }
 
+impl TryFrom<[u8; 3]> for Code {
+    type Error = Error;
+
+    /// Get the code with the given digits, in the order triangle, square,
+    /// circle. See [`Code::from_digits`].
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::{self, Code};
+    /// assert_eq!(Code::try_from([3, 5, 1]), Code::from_digits(3, 5, 1));
+    /// assert_eq!(Code::try_from([6, 1, 1]), Err(code::Error::InvalidDigits));
+    /// ```
+    fn try_from([triangle, square, circle]: [u8; 3]) -> Result<Self, Self::Error> {
+        Code::from_digits(triangle, square, circle)
+    }
+}
+
+impl From<Code> for [u8; 3] {
+    /// Get the digits of the code, in the order triangle, square, circle.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::code::Code;
+    /// let digits: [u8; 3] = Code::from_digits(3, 5, 1)?.into();
+    /// assert_eq!(digits, [3, 5, 1]);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    fn from(code: Code) -> Self {
+        code.digits_array()
+    }
+}
+
 /// A struct representing a set of codes.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Default)]
 pub struct Set {
@@ -251,7 +597,7 @@ pub struct Set {
 }
 
 impl Debug for Set {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         writeln!(f, "△ □ ○")?;
         for code in self.into_iter() {
             writeln!(f, "{} {} {}", code.triangle(), code.square(), code.circle())?;
@@ -270,6 +616,64 @@ impl Set {
         }
     }
 
+    /// Create a new code set containing only the provided code. This is the
+    /// same as [`Set::new_from_code`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use turing_machine_ai::code::{Code, Set};
+    ///
+    /// let code = Code::from_digits(3, 5, 1)?;
+    /// assert_eq!(Set::singleton(code), Set::new_from_code(code));
+    /// assert_eq!(Set::singleton(code).size(), 1);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn singleton(code: Code) -> Self {
+        Set::new_from_code(code)
+    }
+
+    /// Create a new code set containing the provided codes. For sets of
+    /// literal codes, see the [`set!`](crate::set) macro.
+    ///
+    /// # Example
+    /// ```rust
+    /// use turing_machine_ai::code::{Code, Set};
+    ///
+    /// let code_a = Code::from_digits(1, 2, 3)?;
+    /// let code_b = Code::from_digits(4, 5, 1)?;
+    /// let set = Set::from_codes(&[code_a, code_b]);
+    /// assert_eq!(set.size(), 2);
+    /// assert!(set.contains(code_a) && set.contains(code_b));
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub const fn from_codes(codes: &[Code]) -> Self {
+        let mut code_bitmap = 0;
+        let mut index = 0;
+        while index < codes.len() {
+            code_bitmap |= codes[index].bits.get();
+            index += 1;
+        }
+        Set { code_bitmap }
+    }
+
+    /// Returns whether this set contains exactly one code.
+    ///
+    /// # Example
+    /// ```rust
+    /// use turing_machine_ai::code::{Code, Set};
+    ///
+    /// assert!(Set::singleton(Code::from_digits(3, 5, 1)?).is_singleton());
+    /// assert!(!Set::empty().is_singleton());
+    /// assert!(!Set::all().is_singleton());
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn is_singleton(self) -> bool {
+        self.code_bitmap.is_power_of_two()
+    }
+
     /// Insert the given code into this code set.
     ///
     /// # Examples
@@ -314,7 +718,7 @@ impl Set {
     /// assert_eq!(empty_set.size(), 0);
     /// ```
     #[must_use]
-    pub fn empty() -> Set {
+    pub const fn empty() -> Set {
         Set { code_bitmap: 0 }
     }
 
@@ -327,10 +731,20 @@ impl Set {
     /// let complete_set = Set::all();
     /// assert_eq!(complete_set.size(), 125);
     /// ```
+    ///
+    /// Both [`Set::all`] and [`Set::empty`] can be used in constants.
+    /// ```rust
+    /// use turing_machine_ai::code::Set;
+    ///
+    /// const FULL: Set = Set::all();
+    /// const EMPTY: Set = Set::empty();
+    /// assert_eq!(FULL.size(), 125);
+    /// assert_eq!(EMPTY.size(), 0);
+    /// ```
     #[must_use]
-    pub fn all() -> Set {
+    pub const fn all() -> Set {
         Set {
-            code_bitmap: (1 << 125) - 1,
+            code_bitmap: (1 << CODE_COUNT) - 1,
         }
     }
 
@@ -347,6 +761,27 @@ impl Set {
         self.code_bitmap.count_ones()
     }
 
+    /// Count the codes in this set that satisfy the predicate.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::code::Set;
+    /// let even_triangle = Set::all().count_matching(|code| code.triangle() % 2 == 0);
+    /// assert_eq!(even_triangle, 50);
+    /// ```
+    #[must_use]
+    pub fn count_matching(self, predicate: impl Fn(Code) -> bool) -> u32 {
+        self.into_iter()
+            .map(|code| u32::from(predicate(code)))
+            .sum()
+    }
+
+    /// Go through the codes in this set together with their index. See
+    /// [`Code::to_index`].
+    pub fn iter_with_index(self) -> impl Iterator<Item = (usize, Code)> {
+        self.into_iter().map(|code| (code.to_index(), code))
+    }
+
     /// Construct a new code set based on a closure that returns `true` for any
     /// code that must be in the set.
     pub fn from_closure(checker: fn(Code) -> bool) -> Self {
@@ -356,6 +791,43 @@ impl Set {
             .collect()
     }
 
+    /// Construct the set of all codes whose triangle digit satisfies the
+    /// predicate.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::code::{Code, Set};
+    /// let set = Set::where_triangle(|triangle| triangle < 3);
+    /// assert_eq!(set.size(), 50);
+    /// assert!(set.contains(Code::from_digits(2, 5, 5)?));
+    /// assert!(!set.contains(Code::from_digits(3, 1, 1)?));
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    pub fn where_triangle(predicate: impl Fn(u8) -> bool) -> Self {
+        Set::all()
+            .into_iter()
+            .filter(|code| predicate(code.triangle()))
+            .collect()
+    }
+
+    /// Construct the set of all codes whose square digit satisfies the
+    /// predicate.
+    pub fn where_square(predicate: impl Fn(u8) -> bool) -> Self {
+        Set::all()
+            .into_iter()
+            .filter(|code| predicate(code.square()))
+            .collect()
+    }
+
+    /// Construct the set of all codes whose circle digit satisfies the
+    /// predicate.
+    pub fn where_circle(predicate: impl Fn(u8) -> bool) -> Self {
+        Set::all()
+            .into_iter()
+            .filter(|code| predicate(code.circle()))
+            .collect()
+    }
+
     /// Returns whether the given code is part of this set.
     /// ```rust
     /// use turing_machine_ai::code::{Set, Code};
@@ -370,6 +842,70 @@ impl Set {
     pub fn contains(self, code: Code) -> bool {
         (self.code_bitmap & code.bits.get()) != 0
     }
+
+    /// Get the code in this set that differs from the reference in the fewest
+    /// positions. See [`Code::differing_positions`]. Ties are broken by the
+    /// lowest index, see [`Code::to_index`]. If the set is empty, `None` is
+    /// returned.
+    ///
+    /// # Example
+    /// ```rust
+    /// use turing_machine_ai::code::{Code, Set};
+    /// let set = Set::from_codes(&[Code::from_digits(1, 1, 1)?, Code::from_digits(2, 4, 1)?]);
+    /// assert_eq!(set.closest_to(Code::from_digits(2, 4, 5)?), Some(Code::from_digits(2, 4, 1)?));
+    /// assert_eq!(Set::empty().closest_to(Code::from_digits(2, 4, 5)?), None);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn closest_to(self, reference: Code) -> Option<Code> {
+        self.into_iter()
+            .min_by_key(|code| code.differing_positions(reference))
+    }
+
+    /// Split this set into the codes that satisfy the verifier option, and
+    /// those that do not. For a code that satisfies the option, a verifier
+    /// with that option would give a check, and otherwise a cross.
+    ///
+    /// # Example
+    /// ```rust
+    /// use turing_machine_ai::{code::Set, verifier::get_verifier_by_number};
+    ///
+    /// let verifier = get_verifier_by_number(5);
+    /// let (even, odd) = Set::all().partition_by_option(verifier.option(0));
+    /// assert_eq!(even.size(), 50);
+    /// assert_eq!(odd.size(), 75);
+    /// ```
+    #[must_use]
+    pub fn partition_by_option(self, option: &VerifierOption) -> (Set, Set) {
+        let matching = option.code_set();
+        (
+            self.intersected_with(matching),
+            Set {
+                code_bitmap: self.code_bitmap & !matching.code_bitmap,
+            },
+        )
+    }
+
+    /// Format the codes in this set as CSV, with a header row followed by
+    /// one row per code.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::code::{Code, Set};
+    ///
+    /// let set = Set::new_from_code(Code::from_digits(3, 5, 1)?);
+    /// assert_eq!(set.to_csv(), "triangle,square,circle\n3,5,1\n");
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn to_csv(self) -> String {
+        let mut csv = String::from("triangle,square,circle\n");
+        for code in self {
+            let (triangle, square, circle) = code.digits();
+            csv.push_str(&format!("{triangle},{square},{circle}\n"));
+        }
+        csv
+    }
 }
 
 impl IntoIterator for Set {
@@ -377,32 +913,70 @@ impl IntoIterator for Set {
     type Item = Code;
     fn into_iter(self) -> Self::IntoIter {
         SetIterator {
-            set: self,
-            current: 1,
+            remaining: self.code_bitmap,
         }
     }
 }
 
-/// The iterator for a set.
+/// Construct a [`Set`] from codes written as `(triangle, square, circle)`
+/// tuples. The digits are checked at compile time.
+///
+/// # Example
+/// ```rust
+/// use turing_machine_ai::{code::Code, set};
+///
+/// let set = set![(1, 2, 3), (4, 5, 1)];
+/// assert_eq!(set.size(), 2);
+/// assert!(set.contains(Code::from_digits(4, 5, 1)?));
+/// # Ok::<(), turing_machine_ai::code::Error>(())
+/// ```
+///
+/// Invalid digits do not compile:
+/// ```compile_fail
+/// use turing_machine_ai::set;
+///
+/// let set = set![(1, 2, 6)];
+/// ```
+#[macro_export]
+macro_rules! set {
+    ($(($triangle:expr, $square:expr, $circle:expr)),* $(,)?) => {
+        $crate::code::Set::from_codes(&[$(
+            const {
+                match $crate::code::Code::from_digits($triangle, $square, $circle) {
+                    ::core::result::Result::Ok(code) => code,
+                    ::core::result::Result::Err(_) => ::core::panic!("invalid code in set!"),
+                }
+            }
+        ),*])
+    };
+}
+
+/// The iterator for a set. Codes are returned in order of increasing index,
+/// or in reverse using [`Iterator::rev`].
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub struct SetIterator {
-    set: Set,
-    current: u128,
+    /// The codes that have not been returned yet.
+    remaining: u128,
 }
 
 impl Iterator for SetIterator {
     type Item = Code;
     fn next(&mut self) -> Option<Self::Item> {
-        while self.current < (1 << 125) {
-            let code = Code {
-                bits: self.current.try_into().unwrap(),
-            };
-            self.current <<= 1;
-            if self.set.contains(code) {
-                return Some(code);
-            }
-        }
-        None
+        let remaining = NonZeroU128::new(self.remaining)?;
+        // Isolate the lowest set bit.
+        let bits = NonZeroU128::new(remaining.get() & remaining.get().wrapping_neg())?;
+        self.remaining ^= bits.get();
+        Some(Code { bits })
+    }
+}
+
+impl DoubleEndedIterator for SetIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let remaining = NonZeroU128::new(self.remaining)?;
+        // Isolate the highest set bit.
+        let bits = NonZeroU128::new(1 << (u128::BITS - 1 - remaining.leading_zeros()))?;
+        self.remaining ^= bits.get();
+        Some(Code { bits })
     }
 }
 
@@ -417,6 +991,13 @@ impl FromIterator<Code> for Set {
     }
 }
 
+impl<'a> FromIterator<&'a Code> for Set {
+    /// Create a new code set containing all codes in the iterator.
+    fn from_iter<T: IntoIterator<Item = &'a Code>>(iter: T) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
 impl FromIterator<Set> for Set {
     fn from_iter<T: IntoIterator<Item = Set>>(iter: T) -> Self {
         let mut code_set = Set::empty();
@@ -427,9 +1008,47 @@ impl FromIterator<Set> for Set {
     }
 }
 
+/// Codes are serialized as a list of their digits in the order triangle,
+/// square, circle, such as `[2, 4, 1]`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Code {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.digits_array().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Code {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        Code::try_from(<[u8; 3]>::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// Sets are serialized as a list of their codes, such as
+/// `[[2, 4, 1], [3, 5, 1]]`, in the order of iteration.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Set {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(*self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Set {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(alloc::vec::Vec::<Code>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Code, Set};
+    use super::{Code, Color, Set, CODE_COUNT};
+    use crate::game::Game;
+    use crate::gametree::State;
+    use crate::verifier::get_verifier_by_number;
 
     #[test]
     fn test_code_set() {
@@ -438,6 +1057,161 @@ mod tests {
         assert!(!code_set.contains(Code::from_digits(3, 2, 1).unwrap()));
     }
 
+    #[test]
+    fn test_collect_code_references() -> Result<(), super::Error> {
+        let codes = vec![
+            Code::from_digits(1, 2, 3)?,
+            Code::from_digits(5, 4, 3)?,
+            Code::from_digits(1, 2, 3)?,
+        ];
+        let set: Set = codes.iter().collect();
+        assert_eq!(set.size(), 2);
+        assert!(codes.iter().all(|code| set.contains(*code)));
+        assert_eq!(set, codes.into_iter().collect());
+        Ok(())
+    }
+
+    /// Construct a code with [`Code::from_digits_unchecked`], checking the
+    /// digits first.
+    fn from_valid_digits(triangle: u8, square: u8, circle: u8) -> Code {
+        assert!([triangle, square, circle]
+            .iter()
+            .all(|digit| (1..=5).contains(digit)));
+        // SAFETY: All digits were checked to be in the range `1..=5`.
+        unsafe { Code::from_digits_unchecked(triangle, square, circle) }
+    }
+
+    #[test]
+    fn test_from_digits_unchecked() {
+        for code in Set::all() {
+            let (triangle, square, circle) = code.digits();
+            assert_eq!(from_valid_digits(triangle, square, circle), code);
+            assert_eq!(Code::from_digits(triangle, square, circle), Ok(code));
+        }
+    }
+
+    #[test]
+    fn test_from_index() {
+        // The constant can size arrays at compile time.
+        const CODES: [Option<Code>; CODE_COUNT] = {
+            let mut codes = [None; CODE_COUNT];
+            let mut index = 0;
+            while index < CODE_COUNT {
+                codes[index] = Code::from_index(index);
+                index += 1;
+            }
+            codes
+        };
+        assert_eq!(Set::all().size() as usize, CODE_COUNT);
+        for (index, code) in Set::all().iter_with_index() {
+            assert_eq!(CODES[index], Some(code));
+            assert_eq!(Code::from_index(code.to_index()), Some(code));
+        }
+        assert_eq!(Code::from_index(CODE_COUNT), None);
+        assert_eq!(Code::from_index(usize::MAX), None);
+    }
+
+    #[test]
+    fn test_closest_to() -> Result<(), super::Error> {
+        let set = Set::from_codes(&[
+            Code::from_digits(3, 5, 2)?,
+            Code::from_digits(1, 5, 3)?,
+            Code::from_digits(1, 2, 4)?,
+        ]);
+        let reference = Code::from_digits(1, 2, 3)?;
+        // Both 153 and 124 differ in one position; 153 has the lower index.
+        assert_eq!(set.closest_to(reference), Some(Code::from_digits(1, 5, 3)?));
+        assert_eq!(
+            set.closest_to(Code::from_digits(3, 5, 1)?),
+            Some(Code::from_digits(3, 5, 2)?)
+        );
+        // A member of the set is closest to itself.
+        assert!(set
+            .into_iter()
+            .all(|code| set.closest_to(code) == Some(code)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rotate_and_swap() {
+        for code in Set::all() {
+            assert_eq!(code.rotate().rotate().rotate(), code);
+            assert!(code.rotate().same_multiset(code));
+            assert_eq!(
+                code.swap(Color::Triangle, Color::Square)
+                    .swap(Color::Triangle, Color::Square),
+                code
+            );
+            assert_eq!(
+                code.swap(Color::Square, Color::Circle).circle(),
+                code.square()
+            );
+        }
+    }
+
+    #[test]
+    fn test_iter_with_index() {
+        let set = Set::from_closure(|code| code.square() == 3);
+        let mut previous_index = None;
+        for (index, code) in set.iter_with_index() {
+            assert_eq!(index, code.to_index());
+            let (triangle, square, circle) = code.digits();
+            assert_eq!(
+                index,
+                usize::from(triangle - 1)
+                    + 5 * usize::from(square - 1)
+                    + 25 * usize::from(circle - 1)
+            );
+            assert!(previous_index < Some(index));
+            previous_index = Some(index);
+        }
+        assert_eq!(set.iter_with_index().count(), 25);
+    }
+
+    #[test]
+    fn test_set_macro() -> Result<(), super::Error> {
+        let codes = [Code::from_digits(1, 2, 3)?, Code::from_digits(5, 4, 3)?];
+        assert_eq!(crate::set![(1, 2, 3), (5, 4, 3)], Set::from_codes(&codes));
+        assert_eq!(crate::set![(1, 2, 3), (5, 4, 3),], codes.iter().collect());
+        assert_eq!(crate::set![], Set::empty());
+        assert_eq!(Set::from_codes(&[codes[0], codes[0]]).size(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_iterator_rev() {
+        let set = Set::from_closure(|code| code.digit_sum() % 3 == 0);
+        let forward: Vec<Code> = set.into_iter().collect();
+        let mut backward: Vec<Code> = set.into_iter().rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(forward.len(), set.size() as usize);
+        assert_eq!(
+            Set::all().into_iter().next_back(),
+            Code::from_digits(5, 5, 5).ok()
+        );
+    }
+
+    #[test]
+    fn test_partition_by_option() {
+        let set = Set::from_closure(|code| code.digit_sum() > 6);
+        for option in get_verifier_by_number(14).options() {
+            let (matching, non_matching) = set.partition_by_option(option);
+            assert_eq!(matching.size() + non_matching.size(), set.size());
+            assert_eq!(matching.union_with(non_matching), set);
+        }
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+        let possible_codes = State::new(&game).possible_codes();
+        let csv = possible_codes.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("triangle,square,circle"));
+        assert_eq!(lines.count(), possible_codes.size() as usize);
+    }
+
     use proptest::prelude::*;
 
     proptest! {
@@ -0,0 +1,117 @@
+//! This module contains an error type that combines the errors of all
+//! modules.
+
+use thiserror::Error as ThisError;
+
+use crate::code;
+#[cfg(feature = "std")]
+use crate::{
+    game::{GameError, GameParseError},
+    gametree::{AfterMoveError, FindMoveError, MoveParseError, VerifierSolutionParseError},
+};
+
+/// An error that may be returned by any part of this crate. Every module error
+/// converts into it, so that errors can be propagated using `?`. The original
+/// error is available as its source.
+///
+/// # Example
+/// ```
+/// use turing_machine_ai::{code::Code, game::Game, gametree::State};
+///
+/// fn first_check(numbers: &str) -> Result<bool, turing_machine_ai::Error> {
+///     let game: Game = numbers.parse()?;
+///     let state = State::new(&game);
+///     let (state, _) = state.after_move("code 111".parse()?)?;
+///     Ok(state.possible_codes().contains(Code::from_digits(2, 4, 1)?))
+/// }
+///
+/// assert_eq!(first_check("4 9 11 14"), Ok(true));
+/// assert!(first_check("4 9 11 49").is_err());
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, ThisError, Hash)]
+pub enum Error {
+    /// A code could not be constructed.
+    #[error("invalid code")]
+    Code(#[from] code::Error),
+    /// A game could not be parsed.
+    #[cfg(feature = "std")]
+    #[error("invalid game")]
+    GameParse(#[from] GameParseError),
+    /// A game could not be edited.
+    #[cfg(feature = "std")]
+    #[error("invalid change to the game")]
+    Game(#[from] GameError),
+    /// A verifier answer could not be parsed.
+    #[cfg(feature = "std")]
+    #[error("invalid verifier answer")]
+    VerifierSolutionParse(#[from] VerifierSolutionParseError),
+    /// A move could not be parsed.
+    #[cfg(feature = "std")]
+    #[error("invalid move")]
+    MoveParse(#[from] MoveParseError),
+    /// A move could not be applied to the game state.
+    #[cfg(feature = "std")]
+    #[error("the move could not be performed")]
+    AfterMove(#[from] AfterMoveError),
+    /// No move could be found for a game.
+    #[cfg(feature = "std")]
+    #[error("no move could be found")]
+    FindMove(#[from] FindMoveError),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use super::Error;
+    use crate::{
+        code,
+        game::{GameError, GameParseError},
+        gametree::{AfterMoveError, FindMoveError, MoveParseError, VerifierSolutionParseError},
+    };
+
+    #[test]
+    fn test_conversions() {
+        let errors = [
+            (
+                Error::from(code::Error::InvalidDigits),
+                "invalid code",
+                "the provided digits do not form a valid code",
+            ),
+            (
+                Error::from(GameParseError::UnknownVerifier(49)),
+                "invalid game",
+                "there is no verifier with number 49",
+            ),
+            (
+                Error::from(GameError::TooManyVerifiers),
+                "invalid change to the game",
+                "a game can have at most 6 verifiers",
+            ),
+            (
+                Error::from(VerifierSolutionParseError('y')),
+                "invalid verifier answer",
+                "'y' is not a verifier answer, expected 'x' or 'v'",
+            ),
+            (
+                Error::from(MoveParseError::InvalidFormat),
+                "invalid move",
+                "expected 'code', 'verifier' or 'answer' followed by a single value",
+            ),
+            (
+                Error::from(AfterMoveError::NoCodesLeft),
+                "the move could not be performed",
+                "there are no solutions left for this game state",
+            ),
+            (
+                Error::from(FindMoveError::NoSolution),
+                "no move could be found",
+                "the game does not have a solution",
+            ),
+        ];
+        for (error, message, source) in errors {
+            assert_eq!(error.to_string(), message);
+            assert_eq!(error.source().unwrap().to_string(), source);
+        }
+    }
+}
@@ -1,16 +1,24 @@
 //! This module contains code related to a full game but not to game state.
-//! 
+//!
 //! In other words, deductions based on verifiers are performed here, but no
 //! logic for checking codes and verifiers.
 
-use std::{fmt::Debug, iter};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    iter,
+    str::FromStr,
+};
 
 use arrayvec::ArrayVec;
+use thiserror::Error;
 
 use crate::{
-    code::Set,
-    gametree::State,
-    verifier::{get_verifier_by_number, Intersection, Verifier, VerifierOption},
+    code::{Code, Set},
+    gametree::{GameScore, Move, PlayStep, State, VerifierSolution},
+    verifier::{
+        get_verifier_by_number, Intersection, Verifier, VerifierOption, NUMBER_OF_VERIFIERS,
+    },
 };
 
 /// The maximum amount of verifiers allowed in a game.
@@ -24,7 +32,7 @@ pub struct Game {
 
 impl Debug for Game {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (verifier, letter) in self.verifiers.iter().zip('A'..) {
+        for (letter, verifier) in self.iter_verifiers() {
             writeln!(f, "Verifier {letter}")?;
             writeln!(f, "{verifier:?}")?;
         }
@@ -32,6 +40,201 @@ impl Debug for Game {
     }
 }
 
+impl IntoIterator for Game {
+    type Item = Verifier;
+    type IntoIter = std::vec::IntoIter<Verifier>;
+
+    /// Consume the game into its verifiers, in order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.verifiers.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Game {
+    type Item = &'a Verifier;
+    type IntoIter = std::slice::Iter<'a, Verifier>;
+
+    /// Go through the verifiers of the game, in order.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::game::Game;
+    ///
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    /// for verifier in &game {
+    ///     assert!(verifier.number().is_some());
+    /// }
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.verifiers.iter()
+    }
+}
+
+/// An error that may be returned when parsing a [`Game`] from a string.
+#[derive(Clone, Eq, PartialEq, Debug, Error, Hash)]
+pub enum GameParseError {
+    /// Returned when part of the input is not a number.
+    #[error("'{0}' is not a valid verifier number")]
+    InvalidNumber(String),
+    /// Returned when a number does not correspond to a verifier card.
+    #[error("there is no verifier with number {0}")]
+    UnknownVerifier(usize),
+    /// Returned when the same verifier is used more than once.
+    #[error("verifier {0} is used more than once")]
+    DuplicateVerifier(usize),
+    /// Returned when the number of verifiers is not supported.
+    #[error("a game must have between 1 and {MAX_VERIFIERS} verifiers, but {0} were given")]
+    InvalidVerifierCount(usize),
+}
+
+/// An error that may be returned when editing a [`Game`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Error, Hash)]
+pub enum GameError {
+    /// Returned when a number does not correspond to a verifier card.
+    #[error("there is no verifier with number {0}")]
+    UnknownVerifier(usize),
+    /// Returned when the verifier is already part of the game.
+    #[error("verifier {0} is already part of the game")]
+    DuplicateVerifier(usize),
+    /// Returned when the game already has the maximum number of verifiers.
+    #[error("a game can have at most {MAX_VERIFIERS} verifiers")]
+    TooManyVerifiers,
+    /// Returned when the chosen verifier is not part of the game.
+    #[error("verifier {0} is not part of the game")]
+    UnknownChosenVerifier(ChosenVerifier),
+}
+
+impl FromStr for Game {
+    type Err = GameParseError;
+
+    /// Parse a game from its verifier numbers, separated by commas and/or
+    /// whitespace.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::game::{Game, GameParseError};
+    ///
+    /// let game: Game = "3,7,10,14".parse()?;
+    /// assert_eq!(game, Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied()));
+    /// assert_eq!("12 16 18 19 21".parse::<Game>()?.verifier_count(), 5);
+    /// # Ok::<(), GameParseError>(())
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let verifier_numbers = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                part.parse()
+                    .map_err(|_| GameParseError::InvalidNumber(part.to_string()))
+            })
+            .collect::<Result<Vec<usize>, _>>()?;
+        validate_verifier_numbers(&verifier_numbers)?;
+        Ok(Game::new_from_verifier_numbers(
+            verifier_numbers.into_iter(),
+        ))
+    }
+}
+
+/// Count the well-posed games consisting of `verifier_count` distinct
+/// verifiers from the pool of verifier numbers. See [`Game::is_well_posed`].
+///
+/// # Panics
+/// This function panics if the pool contains a number that does not
+/// correspond to a verifier card.
+#[must_use]
+pub fn count_well_posed(pool: &[usize], verifier_count: usize) -> usize {
+    well_posed_games(pool, verifier_count).count()
+}
+
+/// Go through the well-posed games consisting of `verifier_count` distinct
+/// verifiers from the pool of verifier numbers. The games are constructed
+/// lazily, in the order of the pool. See [`Game::is_well_posed`].
+///
+/// # Panics
+/// This function panics if the pool contains a number that does not
+/// correspond to a verifier card.
+///
+/// # Example
+/// ```
+/// use turing_machine_ai::game::{well_posed_games, Game};
+///
+/// let game: Game = "4 9 11 14".parse()?;
+/// assert!(well_posed_games(&[1, 4, 9, 11, 14], 4).any(|candidate| candidate == game));
+/// # Ok::<(), turing_machine_ai::Error>(())
+/// ```
+pub fn well_posed_games(pool: &[usize], verifier_count: usize) -> impl Iterator<Item = Game> {
+    // Construct every verifier only once.
+    let verifiers: Vec<Verifier> = pool.iter().copied().map(get_verifier_by_number).collect();
+    Combinations::new(verifiers.len(), verifier_count).filter_map(move |indices| {
+        let game = Game {
+            verifiers: indices
+                .into_iter()
+                .map(|index| verifiers[index].clone())
+                .collect(),
+        };
+        game.is_well_posed().then_some(game)
+    })
+}
+
+/// An iterator over all combinations of `k` distinct indices below `n`, in
+/// lexicographic order.
+struct Combinations {
+    n: usize,
+    next: Option<Vec<usize>>,
+}
+
+impl Combinations {
+    fn new(n: usize, k: usize) -> Self {
+        Combinations {
+            n,
+            next: (k <= n).then(|| (0..k).collect()),
+        }
+    }
+}
+
+impl Iterator for Combinations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let current = self.next.take()?;
+        let k = current.len();
+        // Find the last index that can still be increased.
+        if let Some(position) = (0..k)
+            .rev()
+            .find(|&position| current[position] < self.n - k + position)
+        {
+            let mut next = current.clone();
+            next[position] += 1;
+            for later in position + 1..k {
+                next[later] = next[later - 1] + 1;
+            }
+            self.next = Some(next);
+        }
+        Some(current)
+    }
+}
+
+/// Check that the verifier numbers correspond to distinct existing cards,
+/// and that their count is supported.
+fn validate_verifier_numbers(verifier_numbers: &[usize]) -> Result<(), GameParseError> {
+    if !(1..=MAX_VERIFIERS).contains(&verifier_numbers.len()) {
+        return Err(GameParseError::InvalidVerifierCount(verifier_numbers.len()));
+    }
+    if let Some(&number) = verifier_numbers
+        .iter()
+        .find(|number| !(1..=NUMBER_OF_VERIFIERS).contains(number))
+    {
+        return Err(GameParseError::UnknownVerifier(number));
+    }
+    match verifier_numbers
+        .iter()
+        .enumerate()
+        .find(|(index, number)| verifier_numbers[..*index].contains(number))
+    {
+        Some((_, &number)) => Err(GameParseError::DuplicateVerifier(number)),
+        None => Ok(()),
+    }
+}
+
 /// A particular assignment for a game. For example, this might indicate that
 /// for the first verifier, the second option is selected, for the second
 /// verifier the third option, etc.
@@ -46,6 +249,13 @@ impl Assignment {
         self.choice.iter().copied()
     }
 
+    /// Get the option chosen for the given verifier, or `None` if the
+    /// assignment does not contain a choice for it.
+    #[must_use]
+    pub fn option_for(&self, verifier: ChosenVerifier) -> Option<u8> {
+        self.choice.get(verifier.0).copied()
+    }
+
     /// Create an assignment from the individual choices.
     pub fn from_choices<T: Into<ArrayVec<u8, MAX_VERIFIERS>>>(choices: T) -> Self {
         Assignment {
@@ -55,14 +265,17 @@ impl Assignment {
 }
 
 /// Represents a choice of verifier, i.e. verifier 'B'.
-/// 
+///
 /// # Example
 /// ```
 /// use turing_machine_ai::game::ChosenVerifier;
-/// 
+///
 /// let verifier = ChosenVerifier::from(1usize);
-/// assert_eq!(format!("{verifier:?}"), "B");
+/// assert_eq!(format!("{verifier}"), "B");
 /// ```
+///
+/// Since there are only 26 letters, verifiers beyond 'Z' are numbered, i.e.
+/// 'A1', 'B1', etc.
 #[derive(Eq, PartialEq, Copy, Clone, Hash)]
 pub struct ChosenVerifier(usize);
 
@@ -72,9 +285,21 @@ impl From<usize> for ChosenVerifier {
     }
 }
 
+impl Display for ChosenVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The index modulo 26 always fits in a `u8`.
+        #[allow(clippy::cast_possible_truncation)]
+        let letter = char::from(b'A' + (self.0 % 26) as u8);
+        match self.0 / 26 {
+            0 => write!(f, "{letter}"),
+            round => write!(f, "{letter}{round}"),
+        }
+    }
+}
+
 impl Debug for ChosenVerifier {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", ('A'..).nth(self.0).unwrap())
+        Display::fmt(self, f)
     }
 }
 
@@ -93,6 +318,128 @@ impl Game {
         (0..self.verifiers.len()).map(ChosenVerifier)
     }
 
+    /// Returns whether the chosen verifier is part of this game.
+    pub(crate) fn has_verifier(&self, verifier: ChosenVerifier) -> bool {
+        verifier.0 < self.verifiers.len()
+    }
+
+    /// Get the answer the verifier gives for the guessed code, if the option
+    /// with index `secret_option` is its secret option.
+    ///
+    /// # Panics
+    /// This function panics if the verifier is not part of the game, or if
+    /// it does not have an option with index `secret_option`.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::{code::Code, game::Game, gametree::VerifierSolution};
+    ///
+    /// // Suppose the secret option of verifier 4 is "□ > 4".
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    /// let guess = Code::from_digits(1, 5, 1)?;
+    /// assert_eq!(game.verifier_answer(0.into(), 2, guess), VerifierSolution::Check);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn verifier_answer(
+        &self,
+        verifier: ChosenVerifier,
+        secret_option: u8,
+        guess: Code,
+    ) -> VerifierSolution {
+        if self
+            .verfier(verifier)
+            .option(secret_option)
+            .code_set()
+            .contains(guess)
+        {
+            VerifierSolution::Check
+        } else {
+            VerifierSolution::Cross
+        }
+    }
+
+    /// Get all codes that correspond to a verifier option giving the provided
+    /// answer for the code.
+    pub(crate) fn codes_for_answer(
+        &self,
+        verifier: ChosenVerifier,
+        code: Code,
+        answer: VerifierSolution,
+    ) -> Set {
+        self.verfier(verifier)
+            .options()
+            .map(VerifierOption::code_set)
+            .filter(|code_set| code_set.contains(code) == (answer == VerifierSolution::Check))
+            .collect()
+    }
+
+    /// Get the possible solutions that are consistent with every fact, where
+    /// each fact is the answer a verifier gave for a tested code. This gives
+    /// the same codes as playing the facts through [`State::after_move`], but
+    /// without the rules on the order of moves.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::{code::{Code, Set}, game::Game, gametree::VerifierSolution};
+    ///
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    /// let guess = Code::from_digits(1, 1, 1)?;
+    /// assert_eq!(game.codes_consistent_with(&[]), game.possible_solutions());
+    /// assert_eq!(
+    ///     game.codes_consistent_with(&[(0.into(), guess, VerifierSolution::Cross)]),
+    ///     Set::singleton(Code::from_digits(2, 4, 1)?)
+    /// );
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn codes_consistent_with(&self, facts: &[(ChosenVerifier, Code, VerifierSolution)]) -> Set {
+        facts.iter().fold(
+            self.possible_solutions(),
+            |codes, &(verifier, code, answer)| {
+                codes.intersected_with(self.codes_for_answer(verifier, code, answer))
+            },
+        )
+    }
+
+    /// Go through the verifiers of this game, yielding the letter and the
+    /// description of each.
+    pub fn verifier_descriptions(
+        &self,
+    ) -> impl Iterator<Item = (ChosenVerifier, &'static str)> + '_ {
+        self.iter_verifiers()
+            .map(|(chosen_verifier, verifier)| (chosen_verifier, verifier.description()))
+    }
+
+    /// Go through the verifiers of this game, together with their letter.
+    pub fn iter_verifiers(&self) -> impl Iterator<Item = (ChosenVerifier, &Verifier)> + '_ {
+        self.iter_verifier_choices().zip(&self.verifiers)
+    }
+
+    /// Get the verifier with the given letter, in either case. If the game
+    /// has no verifier with that letter, `None` is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::game::Game;
+    ///
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    /// let (chosen_verifier, verifier) = game.verifier_by_letter('B').unwrap();
+    /// assert_eq!(chosen_verifier.to_string(), "B");
+    /// assert_eq!(verifier.number(), Some(9));
+    /// assert!(game.verifier_by_letter('E').is_none());
+    /// ```
+    #[must_use]
+    pub fn verifier_by_letter(&self, letter: char) -> Option<(ChosenVerifier, &Verifier)> {
+        if !letter.is_ascii_alphabetic() {
+            return None;
+        }
+        let index = letter.to_ascii_uppercase() as usize - 'A' as usize;
+        self.verifiers
+            .get(index)
+            .map(|verifier| (ChosenVerifier(index), verifier))
+    }
+
     /// Return the number of verifiers for this game.
     ///
     /// # Example
@@ -122,6 +469,12 @@ impl Game {
         Game { verifiers }
     }
 
+    /// Construct a game from the numbers of its verifier cards. To validate
+    /// numbers from untrusted input, parse the game with [`FromStr`] instead.
+    ///
+    /// # Panics
+    /// This function panics if a number does not correspond to a verifier
+    /// card.
     #[must_use]
     pub fn new_from_verifier_numbers(verifier_numbers: impl Iterator<Item = usize>) -> Game {
         Game {
@@ -129,6 +482,101 @@ impl Game {
         }
     }
 
+    /// Add the verifier with the given card number to the game. It will be
+    /// the last verifier.
+    ///
+    /// # Errors
+    /// Returns a [`GameError`] if there is no verifier with this number, if
+    /// it is already part of the game, or if the game already has the maximum
+    /// number of verifiers.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::game::{Game, GameError};
+    ///
+    /// let mut game = Game::new_from_verifiers(Vec::new());
+    /// for number in [4, 9, 11, 14] {
+    ///     game.add_verifier(number)?;
+    /// }
+    /// assert!(game.is_well_posed());
+    /// assert_eq!(game.add_verifier(9), Err(GameError::DuplicateVerifier(9)));
+    /// # Ok::<(), GameError>(())
+    /// ```
+    pub fn add_verifier(&mut self, number: usize) -> Result<(), GameError> {
+        if !(1..=NUMBER_OF_VERIFIERS).contains(&number) {
+            return Err(GameError::UnknownVerifier(number));
+        }
+        if self
+            .verifiers
+            .iter()
+            .any(|verifier| verifier.number() == Some(number))
+        {
+            return Err(GameError::DuplicateVerifier(number));
+        }
+        if self.verifiers.len() >= MAX_VERIFIERS {
+            return Err(GameError::TooManyVerifiers);
+        }
+        self.verifiers.push(get_verifier_by_number(number));
+        Ok(())
+    }
+
+    /// Remove the given verifier from the game and return it. The letters of
+    /// the verifiers after it shift down by one.
+    ///
+    /// # Errors
+    /// Returns [`GameError::UnknownChosenVerifier`] if the game has no such
+    /// verifier.
+    pub fn remove_verifier(&mut self, verifier: ChosenVerifier) -> Result<Verifier, GameError> {
+        if !self.has_verifier(verifier) {
+            return Err(GameError::UnknownChosenVerifier(verifier));
+        }
+        Ok(self.verifiers.remove(verifier.0))
+    }
+
+    /// Returns whether the same verifier appears more than once in this game.
+    /// Such a game can never be well-posed, since the repeated verifier is
+    /// always redundant.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::game::Game;
+    ///
+    /// let game = Game::new_from_verifier_numbers([3, 7, 3, 14].iter().copied());
+    /// assert!(game.has_duplicate_verifiers());
+    /// ```
+    #[must_use]
+    pub fn has_duplicate_verifiers(&self) -> bool {
+        self.verifiers
+            .iter()
+            .enumerate()
+            .any(|(index, verifier)| self.verifiers[..index].contains(verifier))
+    }
+
+    /// Get a copy of this game with the verifiers sorted by their card
+    /// number. Games that only differ in the order of their verifiers are
+    /// strategically identical, and have the same canonical game. This makes
+    /// the canonical game suitable as a key when deduplicating games.
+    ///
+    /// Note that the verifier letters may change. Verifiers without a card
+    /// number are placed first, in their original order.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::game::Game;
+    ///
+    /// let game = Game::new_from_verifier_numbers([7, 3, 10, 14].iter().copied());
+    /// assert_eq!(
+    ///     game.canonical(),
+    ///     Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied())
+    /// );
+    /// ```
+    #[must_use]
+    pub fn canonical(&self) -> Game {
+        let mut verifiers = self.verifiers.clone();
+        verifiers.sort_by_key(Verifier::number);
+        Game { verifiers }
+    }
+
     /// Get all assignments, regardless of their validity.
     pub fn all_assignments(&self) -> impl Iterator<Item = Assignment> + '_ {
         let len = self.verifiers.len();
@@ -138,7 +586,7 @@ impl Game {
             }),
             move |prev| {
                 let mut new = prev.clone();
-                new.choice[0] += 1;
+                *new.choice.first_mut()? += 1;
                 for index in 0..len {
                     // Carry to the right
                     if usize::from(new.choice[index]) >= self.verifiers[index].number_of_options() {
@@ -164,11 +612,78 @@ impl Game {
             .intersect()
     }
 
-    pub fn print_assigment(&self, assignment: &Assignment) {
-        for (verifier, assignment) in self.verifiers.iter().zip(assignment.choice.iter()) {
-            println!("{}", verifier.description());
-            println!("- {}", verifier.option(*assignment).description);
+    /// Render the verifiers like they are laid out on the table: every
+    /// verifier with its letter and description, followed by its options,
+    /// labelled `a`, `b`, `c`, etc.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::game::Game;
+    ///
+    /// let game = Game::new_from_verifier_numbers([4].iter().copied());
+    /// assert_eq!(
+    ///     game.render_grid(),
+    ///     "A: the □ number compared to 4\n   a) □ < 4\n   b) □ = 4\n   c) □ > 4\n"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn render_grid(&self) -> String {
+        let mut grid = String::new();
+        for (chosen_verifier, verifier) in self.iter_verifiers() {
+            grid.push_str(&format!("{chosen_verifier}: {}\n", verifier.description()));
+            for (option, label) in verifier.options().zip('a'..) {
+                grid.push_str(&format!("   {label}) {}\n", option.description()));
+            }
         }
+        grid
+    }
+
+    /// Describe the assignment in words: for every verifier its letter and
+    /// description, followed by the description of the chosen option.
+    #[must_use]
+    pub fn describe_assignment(&self, assignment: &Assignment) -> String {
+        self.render_assignment(assignment, |chosen_verifier, verifier, option| {
+            format!(
+                "{chosen_verifier}: {}\n   {}\n",
+                verifier.description(),
+                option.description()
+            )
+        })
+    }
+
+    /// Format the assignment as text, listing every verifier description
+    /// followed by the chosen option.
+    #[must_use]
+    pub fn format_assignment(&self, assignment: &Assignment) -> String {
+        self.render_assignment(assignment, |_, verifier, option| {
+            format!("{}\n- {}\n", verifier.description(), option.description())
+        })
+    }
+
+    /// Concatenate the text that `render_verifier` returns for every verifier
+    /// and the option chosen for it in the assignment.
+    fn render_assignment(
+        &self,
+        assignment: &Assignment,
+        render_verifier: impl Fn(ChosenVerifier, &Verifier, &VerifierOption) -> String,
+    ) -> String {
+        self.iter_verifiers()
+            .zip(self.verifier_options_for_assignment(assignment))
+            .map(|((chosen_verifier, verifier), option)| {
+                render_verifier(chosen_verifier, verifier, &option)
+            })
+            .collect()
+    }
+
+    /// Print the assignment to standard output. See
+    /// [`Game::format_assignment`].
+    pub fn print_assignment(&self, assignment: &Assignment) {
+        print!("{}", self.format_assignment(assignment));
+    }
+
+    #[deprecated(note = "use `print_assignment` instead")]
+    pub fn print_assigment(&self, assignment: &Assignment) {
+        self.print_assignment(assignment);
     }
 
     /// Check if the assignment is a possible puzzle solution. This means that
@@ -176,7 +691,10 @@ impl Game {
     /// none of the verifiers are redundant.
     #[must_use]
     pub fn is_possible_solution(&self, assignment: &Assignment) -> bool {
-        if self.possible_codes_for_assignment(assignment).size() != 1 {
+        if !self
+            .possible_codes_for_assignment(assignment)
+            .is_singleton()
+        {
             return false;
         }
 
@@ -197,8 +715,105 @@ impl Game {
         true
     }
 
+    /// Let the solver play the game against the given secret code, answering
+    /// the verifiers as they would for the secret. Every move is returned
+    /// together with the number of codes that remain possible and the score
+    /// predicted by the solver. Verifier answers carry the prediction of the
+    /// move before them.
+    ///
+    /// If the secret is not a possible solution, `None` is returned.
+    ///
+    /// # Panics
+    /// This function panics if some possible solutions cannot be told apart
+    /// by the verifiers, so that the game cannot be solved.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::{code::Code, game::Game};
+    ///
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    /// let steps = game.annotated_play_out(Code::from_digits(2, 4, 1)?).unwrap();
+    /// assert_eq!(steps.len(), 3);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn annotated_play_out(&self, secret: Code) -> Option<Vec<PlayStep>> {
+        let assignment = self.assignment_for_secret(secret)?;
+        let mut state = State::new(self);
+        let mut steps = Vec::new();
+        let mut best_score = None;
+        while !state.is_solved() {
+            let move_made = if let (Some(verifier), Some(code)) =
+                (state.awaiting_verifier(), state.current_code())
+            {
+                let secret_option = assignment.option_for(verifier)?;
+                Move::VerifierSolution(self.verifier_answer(verifier, secret_option, code))
+            } else {
+                let (score, move_to_do) = state.find_best_move();
+                best_score = Some(score);
+                move_to_do
+            };
+            state = state
+                .after_move(move_made)
+                .expect("the moves of the solver should be valid for the secret")
+                .0;
+            steps.push(PlayStep {
+                move_made,
+                candidates_remaining: state.possible_codes().size(),
+                best_score: best_score.expect("a verifier is chosen before it answers"),
+            });
+        }
+        Some(steps)
+    }
+
+    /// Find the secret code that takes the solver the most effort to find,
+    /// together with the score of the game for that secret. See
+    /// [`Game::annotated_play_out`]. If the game is not well-posed, `None` is
+    /// returned.
+    ///
+    /// # Panics
+    /// This function panics if some possible solutions cannot be told apart
+    /// by the verifiers, so that the game cannot be solved.
+    #[must_use]
+    pub fn hardest_secret(&self) -> Option<(Code, GameScore)> {
+        self.possible_solutions()
+            .into_iter()
+            .filter_map(|secret| {
+                let steps = self.annotated_play_out(secret)?;
+                let mut score = GameScore {
+                    codes_guessed: 0,
+                    verifiers_checked: 0,
+                };
+                for step in steps {
+                    match step.move_made {
+                        Move::ChooseNewCode(_) => score.codes_guessed += 1,
+                        Move::ChooseVerifier(_) => score.verifiers_checked += 1,
+                        Move::VerifierSolution(_) => {}
+                    }
+                }
+                Some((secret, score))
+            })
+            .max_by_key(|(_, score)| *score)
+    }
+
     /// Get all possible solutions, i.e. those codes that correspond to a
     /// verifier result that have exactly one solution.
+    ///
+    /// Multiple assignments may lead to the same code, so the number of codes
+    /// may be smaller than the number of assignments that are a possible
+    /// solution. Every code is contained only once.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::{code::{Code, Set}, game::Game};
+    ///
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    /// assert_eq!(
+    ///     game.possible_solutions(),
+    ///     Set::from_codes(&[Code::from_digits(2, 2, 1)?, Code::from_digits(2, 4, 1)?])
+    /// );
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
     #[must_use]
     pub fn possible_solutions(&self) -> Set {
         self.all_assignments()
@@ -206,4 +821,889 @@ impl Game {
             .map(|assignment| self.possible_codes_for_assignment(&assignment))
             .fold(Set::empty(), Set::union_with)
     }
+
+    /// Get all possible solutions like [`Game::possible_solutions`], calling
+    /// `on_progress` with the number of assignments processed so far and the
+    /// total number of assignments after each one. This can be used to report
+    /// progress for games with many verifiers.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::game::Game;
+    ///
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    /// let mut last_progress = None;
+    /// let solutions = game.possible_solutions_with_progress(|processed, total| {
+    ///     last_progress = Some((processed, total));
+    /// });
+    /// assert_eq!(solutions, game.possible_solutions());
+    /// assert_eq!(last_progress, Some((108, 108)));
+    /// ```
+    pub fn possible_solutions_with_progress(
+        &self,
+        mut on_progress: impl FnMut(usize, usize),
+    ) -> Set {
+        let total = self
+            .verifiers
+            .iter()
+            .map(Verifier::number_of_options)
+            .product();
+        let mut solutions = Set::empty();
+        for (index, assignment) in self.all_assignments().enumerate() {
+            if self.is_possible_solution(&assignment) {
+                solutions = solutions.union_with(self.possible_codes_for_assignment(&assignment));
+            }
+            on_progress(index + 1, total);
+        }
+        solutions
+    }
+
+    /// Go through all assignments that are a possible solution, together with
+    /// the code they correspond to. The assignments are yielded in the same
+    /// order as [`Game::all_assignments`]. A code may occur multiple times.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::{code::Code, game::Game};
+    ///
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    /// let codes: Vec<Code> = game.solutions_with_assignments().map(|(code, _)| code).collect();
+    /// assert_eq!(codes, vec![Code::from_digits(2, 4, 1)?, Code::from_digits(2, 2, 1)?]);
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    pub fn solutions_with_assignments(&self) -> impl Iterator<Item = (Code, Assignment)> + '_ {
+        self.all_assignments().filter_map(|assignment| {
+            let code = self
+                .possible_codes_for_assignment(&assignment)
+                .into_iter()
+                .next()?;
+            self.is_possible_solution(&assignment)
+                .then_some((code, assignment))
+        })
+    }
+
+    /// Find the assignment of verifier options that has the given secret as
+    /// its unique solution, without redundant verifiers. If there is no such
+    /// assignment, `None` is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::{code::Code, game::Game};
+    ///
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    /// let assignment = game.assignment_for_secret(Code::from_digits(2, 4, 1)?).unwrap();
+    /// assert_eq!(assignment.choices().collect::<Vec<_>>(), vec![1, 0, 0, 2]);
+    /// assert!(game.assignment_for_secret(Code::from_digits(1, 1, 1)?).is_none());
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn assignment_for_secret(&self, secret: Code) -> Option<Assignment> {
+        self.solutions_with_assignments()
+            .find_map(|(code, assignment)| (code == secret).then_some(assignment))
+    }
+
+    /// Get all possible solutions like [`Game::possible_solutions`], but
+    /// distribute the assignments over multiple threads. Since the result is
+    /// a [`Set`], it is identical to the serial version regardless of the
+    /// order in which assignments are processed.
+    #[cfg(feature = "parallel")]
+    #[must_use]
+    pub fn possible_solutions_parallel(&self) -> Set {
+        use rayon::iter::{ParallelBridge, ParallelIterator};
+        self.all_assignments()
+            .par_bridge()
+            .filter(|assignment| self.is_possible_solution(assignment))
+            .map(|assignment| self.possible_codes_for_assignment(&assignment))
+            .reduce(Set::empty, Set::union_with)
+    }
+
+    /// Get the indices of the options of the given verifier that are still
+    /// possible, i.e. those that appear in at least one possible solution
+    /// whose code is contained in `codes`. The indices are sorted. If the
+    /// verifier is not part of the game, no options are returned.
+    #[must_use]
+    pub fn possible_options(&self, verifier: ChosenVerifier, codes: Set) -> Vec<u8> {
+        if !self.has_verifier(verifier) {
+            return Vec::new();
+        }
+        let mut options: Vec<u8> = self
+            .all_assignments()
+            .filter(|assignment| {
+                self.is_possible_solution(assignment)
+                    && self
+                        .possible_codes_for_assignment(assignment)
+                        .intersected_with(codes)
+                        .size()
+                        > 0
+            })
+            .map(|assignment| assignment.choice[verifier.0])
+            .collect();
+        options.sort_unstable();
+        options.dedup();
+        options
+    }
+
+    /// Check whether this game is well-posed, i.e. whether there is at least
+    /// one assignment that corresponds to a single code without any of the
+    /// verifiers being redundant.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::game::Game;
+    ///
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    /// assert!(game.is_well_posed());
+    /// ```
+    #[must_use]
+    pub fn is_well_posed(&self) -> bool {
+        self.all_assignments()
+            .any(|assignment| self.is_possible_solution(&assignment))
+    }
+
+    /// Get a cheap estimate of how much information the verifiers of this
+    /// game provide, without running the solver. For every verifier, all
+    /// codes are grouped by the options they satisfy. Since options may
+    /// overlap, a code can satisfy several of them, or none. This is the
+    /// entropy in bits of that partition, assuming every code is equally
+    /// likely. The result is the sum over all verifiers. More discriminating
+    /// verifiers give a higher value.
+    #[must_use]
+    pub fn verifier_entropy(&self) -> f64 {
+        let total = f64::from(Set::all().size());
+        self.verifiers
+            .iter()
+            .map(|verifier| {
+                let option_sets: Vec<Set> =
+                    verifier.options().map(VerifierOption::code_set).collect();
+                let mut part_sizes: HashMap<Vec<bool>, u32> = HashMap::new();
+                for code in Set::all() {
+                    let satisfied_options =
+                        option_sets.iter().map(|set| set.contains(code)).collect();
+                    *part_sizes.entry(satisfied_options).or_default() += 1;
+                }
+                part_sizes
+                    .into_values()
+                    .map(|size| {
+                        let probability = f64::from(size) / total;
+                        -probability * probability.log2()
+                    })
+                    .sum::<f64>()
+            })
+            .sum()
+    }
+
+    /// Find all subsets of the verifiers of this game that are well-posed,
+    /// while none of their proper subsets are. The games are returned in
+    /// order of increasing verifier count.
+    ///
+    /// Supersets of subsets that were already found are not checked, since
+    /// they can never be minimal.
+    ///
+    /// # Panics
+    /// This function panics if the game has 32 or more verifiers.
+    #[must_use]
+    pub fn minimal_sufficient_subsets(&self) -> Vec<Game> {
+        let all = self.all_verifiers_mask();
+        let mut sufficient_masks: Vec<u32> = Vec::new();
+        for subset_size in 1..=self.verifiers.len() {
+            for mask in 1..=all {
+                if mask.count_ones() as usize != subset_size
+                    || sufficient_masks
+                        .iter()
+                        .any(|sufficient| mask & sufficient == *sufficient)
+                {
+                    continue;
+                }
+                if self.subset(mask).is_well_posed() {
+                    sufficient_masks.push(mask);
+                }
+            }
+        }
+        sufficient_masks
+            .into_iter()
+            .map(|mask| self.subset(mask))
+            .collect()
+    }
+
+    /// Get the verifiers that can be removed from the game without changing
+    /// its solution. A verifier is returned if the game without it is
+    /// well-posed (see [`Game::is_well_posed`]) and has the same possible
+    /// solutions as this game. A game with a redundant verifier has no
+    /// possible solutions of its own, so in that case the solutions of the
+    /// game without the verifier only have to fit one of its options.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::game::{ChosenVerifier, Game};
+    ///
+    /// // Verifier 3 is not needed to find the solution of 4, 9, 11, 14.
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14, 3].iter().copied());
+    /// assert_eq!(game.redundant_verifiers(), vec![ChosenVerifier::from(4)]);
+    ///
+    /// // Without verifier 14 the game is well-posed as well, but verifier 14
+    /// // rules out some of its solutions. Only verifier 1 is redundant.
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14, 1].iter().copied());
+    /// assert_eq!(game.redundant_verifiers(), vec![ChosenVerifier::from(4)]);
+    /// ```
+    ///
+    /// # Panics
+    /// This function panics if the game has 32 or more verifiers.
+    #[must_use]
+    pub fn redundant_verifiers(&self) -> Vec<ChosenVerifier> {
+        let all = self.all_verifiers_mask();
+        let solutions = self.possible_solutions();
+        self.iter_verifiers()
+            .filter(|(chosen_verifier, verifier)| {
+                let game = self.subset(all & !(1 << chosen_verifier.0));
+                if !game.is_well_posed() {
+                    return false;
+                }
+                let solutions_without = game.possible_solutions();
+                if solutions.size() > 0 {
+                    solutions_without == solutions
+                } else {
+                    solutions_without.intersected_with(verifier.union_of_options())
+                        == solutions_without
+                }
+            })
+            .map(|(chosen_verifier, _)| chosen_verifier)
+            .collect()
+    }
+
+    /// Get the mask for [`Game::subset`] in which the bit of every verifier
+    /// is set.
+    ///
+    /// # Panics
+    /// This function panics if the game has 32 or more verifiers, since their
+    /// bits do not fit in the mask.
+    fn all_verifiers_mask(&self) -> u32 {
+        assert!(
+            self.verifiers.len() < 32,
+            "a game with 32 or more verifiers does not fit in a mask"
+        );
+        (1 << self.verifiers.len()) - 1
+    }
+
+    /// Get the game consisting of only those verifiers whose index bit is set
+    /// in the provided mask.
+    fn subset(&self, mask: u32) -> Game {
+        Game::new_from_verifiers(
+            self.verifiers
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| mask & (1 << index) != 0)
+                .map(|(_, verifier)| verifier.clone())
+                .collect(),
+        )
+    }
+}
+
+/// An error that may be returned by [`load_puzzles`].
+#[cfg(feature = "serde")]
+#[derive(Debug, Error)]
+pub enum LoadError {
+    /// Returned when the input is not a JSON array of objects.
+    #[error("the puzzle file is not valid JSON")]
+    Json(#[from] serde_json::Error),
+    /// Returned when a puzzle does not have a name.
+    #[error("puzzle {0} does not have a name")]
+    MissingName(usize),
+    /// Returned when the verifiers of a puzzle are missing or invalid.
+    #[error("puzzle '{name}' does not have valid verifiers")]
+    InvalidVerifiers {
+        /// The name of the puzzle.
+        name: String,
+        /// The reason the verifiers could not be read.
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// Read a collection of named puzzles from JSON, in the form
+/// `[{"name": "...", "verifiers": [3, 7, 10, 14]}]`. The verifier numbers
+/// are validated in the same way as when deserializing a [`Game`].
+///
+/// # Errors
+/// Returns an error if the input is not valid JSON, if a puzzle is missing
+/// its name, or if its verifiers are missing or invalid.
+///
+/// # Example
+/// ```
+/// use turing_machine_ai::game::{load_puzzles, Game};
+///
+/// let json = r#"[{"name": "Booklet #1", "verifiers": [4, 9, 11, 14]}]"#;
+/// let puzzles = load_puzzles(json.as_bytes())?;
+/// assert_eq!(puzzles, vec![("Booklet #1".to_string(), "4 9 11 14".parse::<Game>()?)]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[cfg(feature = "serde")]
+pub fn load_puzzles(reader: impl std::io::Read) -> Result<Vec<(String, Game)>, LoadError> {
+    use serde::Deserialize;
+    let puzzles: Vec<serde_json::Map<String, serde_json::Value>> = serde_json::from_reader(reader)?;
+    puzzles
+        .into_iter()
+        .enumerate()
+        .map(|(index, puzzle)| {
+            let name = puzzle
+                .get("name")
+                .and_then(serde_json::Value::as_str)
+                .ok_or(LoadError::MissingName(index))?
+                .to_string();
+            let verifiers = puzzle.get("verifiers").unwrap_or(&serde_json::Value::Null);
+            match Game::deserialize(verifiers) {
+                Ok(game) => Ok((name, game)),
+                Err(source) => Err(LoadError::InvalidVerifiers { name, source }),
+            }
+        })
+        .collect()
+}
+
+/// Games are serialized as the list of their (one-indexed) verifier numbers.
+/// Only games consisting of verifiers obtained through
+/// [`get_verifier_by_number`] can be serialized.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Game {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{Error, SerializeSeq};
+        let mut seq = serializer.serialize_seq(Some(self.verifiers.len()))?;
+        for verifier in &self.verifiers {
+            let number = verifier
+                .number()
+                .ok_or_else(|| S::Error::custom("verifier does not have a card number"))?;
+            seq.serialize_element(&number)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Game {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        let verifier_numbers = Vec::<usize>::deserialize(deserializer)?;
+        validate_verifier_numbers(&verifier_numbers).map_err(D::Error::custom)?;
+        Ok(Game::new_from_verifier_numbers(
+            verifier_numbers.into_iter(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        count_well_posed, get_verifier_by_number, well_posed_games, Assignment, ChosenVerifier,
+        Code, Combinations, Game, GameError, GameParseError, Set, State, Verifier,
+        VerifierSolution,
+    };
+
+    #[test]
+    fn test_display_chosen_verifier() {
+        assert_eq!(ChosenVerifier::from(0).to_string(), "A");
+        assert_eq!(ChosenVerifier::from(5).to_string(), "F");
+        assert_eq!(ChosenVerifier::from(26).to_string(), "A1");
+        assert_eq!(format!("{:?}", ChosenVerifier::from(53)), "B2");
+    }
+
+    #[test]
+    fn test_render_grid() {
+        let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+        let grid = game.render_grid();
+        for (chosen_verifier, letter) in game.iter_verifier_choices().zip(["A", "B", "C", "D"]) {
+            let description = game.verfier(chosen_verifier).description();
+            assert!(grid.contains(&format!("{letter}: {description}")));
+        }
+        assert!(grid.contains("   d) three 4s"));
+    }
+
+    #[test]
+    fn test_canonical() {
+        let game = Game::new_from_verifier_numbers([7, 3, 10, 14].iter().copied());
+        let other_game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+        assert_ne!(game, other_game);
+        assert_eq!(game.canonical(), other_game.canonical());
+    }
+
+    #[test]
+    fn test_has_duplicate_verifiers() {
+        let game = Game::new_from_verifier_numbers([3, 7, 10, 7].iter().copied());
+        assert!(game.has_duplicate_verifiers());
+        let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+        assert!(!game.has_duplicate_verifiers());
+
+        // A custom copy of a card is a duplicate of that card.
+        let card = get_verifier_by_number(7);
+        let copy = Verifier::from_description_and_options(
+            card.description(),
+            &card.options().copied().collect::<Vec<_>>(),
+        );
+        let game = Game::new_from_verifiers(vec![card, get_verifier_by_number(3), copy]);
+        assert!(game.has_duplicate_verifiers());
+    }
+
+    #[test]
+    fn test_count_well_posed() {
+        let pool = [1, 4, 9, 11, 14];
+        // Only verifier 9 can identify a code on its own, namely 333.
+        assert_eq!(count_well_posed(&pool, 1), 1);
+        assert_eq!(count_well_posed(&pool, 4), 2);
+        // Verifier 1 is redundant when all others are present.
+        assert_eq!(count_well_posed(&pool, 5), 0);
+    }
+
+    #[test]
+    fn test_well_posed_games() {
+        let pool = [1, 4, 9, 11, 14];
+        let games: Vec<Game> = well_posed_games(&pool, 4).take(2).collect();
+        assert_eq!(games.len(), 2);
+        assert!(games.iter().all(Game::is_well_posed));
+    }
+
+    #[test]
+    fn test_combinations() {
+        let combinations: Vec<Vec<usize>> = Combinations::new(4, 2).collect();
+        assert_eq!(
+            combinations,
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3]
+            ]
+        );
+        assert_eq!(Combinations::new(2, 0).count(), 1);
+        assert_eq!(Combinations::new(2, 3).count(), 0);
+    }
+
+    #[test]
+    fn test_describe_assignment() {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let assignment = Assignment::from_choices([1, 0, 2, 1, 0, 0]);
+        assert_eq!(
+            game.describe_assignment(&assignment),
+            "A: the □ number compared to 4
+   □ = 4
+B: the number of 3s in the code
+   zero 3s
+C: the △ number compared to the □ number
+   △ > □
+D: which colour's number is smaller than either of the others
+   □ < △, ○
+"
+        );
+    }
+
+    #[test]
+    fn test_format_assignment() {
+        let game = Game::new_from_verifier_numbers([4, 9].iter().copied());
+        let assignment = Assignment::from_choices([1, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            game.format_assignment(&assignment),
+            "the □ number compared to 4
+- □ = 4
+the number of 3s in the code
+- zero 3s
+"
+        );
+    }
+
+    #[test]
+    fn test_verifier_entropy() {
+        let comparisons = Game::new_from_verifier_numbers([11, 12, 13].iter().copied());
+        let parities = Game::new_from_verifier_numbers([5, 6, 7].iter().copied());
+        assert!(comparisons.verifier_entropy() > parities.verifier_entropy());
+        assert!((Game::new_from_verifiers(Vec::new()).verifier_entropy()).abs() < f64::EPSILON);
+
+        // No option of verifier 14 holds for the 35 codes with a tie for the
+        // smallest number, so they form a part of their own.
+        let entropy = |part_sizes: &[u32]| -> f64 {
+            part_sizes
+                .iter()
+                .map(|&size| {
+                    let probability = f64::from(size) / 125.0;
+                    -probability * probability.log2()
+                })
+                .sum()
+        };
+        let smallest = Game::new_from_verifier_numbers([14].iter().copied());
+        assert!((smallest.verifier_entropy() - entropy(&[30, 30, 30, 35])).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_add_and_remove_verifier() {
+        let mut game = Game::new_from_verifiers(Vec::new());
+        for number in [4, 9, 11, 14] {
+            assert_eq!(game.add_verifier(number), Ok(()));
+        }
+        assert_eq!(
+            game,
+            Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied())
+        );
+        assert_eq!(game.add_verifier(0), Err(GameError::UnknownVerifier(0)));
+        assert_eq!(game.add_verifier(49), Err(GameError::UnknownVerifier(49)));
+        assert_eq!(game.add_verifier(11), Err(GameError::DuplicateVerifier(11)));
+        assert_eq!(game.add_verifier(1), Ok(()));
+        assert_eq!(game.add_verifier(2), Ok(()));
+        assert_eq!(game.add_verifier(3), Err(GameError::TooManyVerifiers));
+
+        assert_eq!(
+            game.remove_verifier(ChosenVerifier(1)),
+            Ok(get_verifier_by_number(9))
+        );
+        assert_eq!(
+            game,
+            Game::new_from_verifier_numbers([4, 11, 14, 1, 2].iter().copied())
+        );
+        assert!(!game.is_well_posed());
+    }
+
+    #[test]
+    fn test_remove_missing_verifier() {
+        let mut game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let error = game.remove_verifier(ChosenVerifier(4)).unwrap_err();
+        assert_eq!(error, GameError::UnknownChosenVerifier(ChosenVerifier(4)));
+        assert_eq!(error.to_string(), "verifier E is not part of the game");
+        assert_eq!(game.verifier_count(), 4);
+    }
+
+    #[test]
+    fn test_possible_solutions() {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        assert!(game.is_well_posed());
+        assert_eq!(game.possible_solutions().size(), 2);
+        // A single verifier never singles out one code, so no assignment is a
+        // possible solution.
+        let game = Game::new_from_verifier_numbers([4].iter().copied());
+        assert!(!game.is_well_posed());
+        assert_eq!(game.possible_solutions(), Set::empty());
+    }
+
+    #[test]
+    fn test_possible_solutions_with_progress() {
+        let game = Game::new_from_verifier_numbers([12, 16, 18, 19, 21].iter().copied());
+        let mut calls = Vec::new();
+        let solutions = game
+            .possible_solutions_with_progress(|processed, total| calls.push((processed, total)));
+        assert_eq!(solutions, game.possible_solutions());
+        assert_eq!(calls.len(), game.all_assignments().count());
+        let (processed, total) = *calls.last().unwrap();
+        assert_eq!(processed, total);
+        assert!(
+            calls
+                .iter()
+                .enumerate()
+                .all(|(index, &(processed, call_total))| processed == index + 1
+                    && call_total == total)
+        );
+    }
+
+    #[test]
+    fn test_possible_solutions_shared_codes() {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 48].iter().copied());
+        let solution_assignments: Vec<_> = game
+            .all_assignments()
+            .filter(|assignment| game.is_possible_solution(assignment))
+            .collect();
+        assert_eq!(solution_assignments.len(), 6);
+        let possible_solutions = game.possible_solutions();
+        assert_eq!(possible_solutions.size(), 4);
+        assert_eq!(game.solutions_with_assignments().count(), 6);
+        assert!(game
+            .solutions_with_assignments()
+            .map(|(code, assignment)| (code, solution_assignments.contains(&assignment)))
+            .all(|(code, known)| known && possible_solutions.contains(code)));
+        assert!(solution_assignments.iter().all(|assignment| {
+            possible_solutions.contains(
+                game.possible_codes_for_assignment(assignment)
+                    .into_iter()
+                    .next()
+                    .unwrap(),
+            )
+        }));
+    }
+
+    #[test]
+    fn test_redundant_verifiers() {
+        let base = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        assert!(base.is_well_posed());
+        assert!(base.redundant_verifiers().is_empty());
+
+        // Verifier 3 is superfluous, removing it restores the original game.
+        let mut game = base.clone();
+        game.add_verifier(3).unwrap();
+        assert!(!game.is_well_posed());
+        assert_eq!(game.redundant_verifiers(), vec![ChosenVerifier(4)]);
+        game.remove_verifier(ChosenVerifier(4)).unwrap();
+        assert_eq!(game.possible_solutions(), base.possible_solutions());
+
+        // Without verifier 1 or without verifier 14 the game is well-posed,
+        // but verifier 14 rules out some solutions of the game without it.
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14, 1].iter().copied());
+        let without_1 = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let without_14 = Game::new_from_verifier_numbers([4, 9, 11, 1].iter().copied());
+        assert!(without_1.is_well_posed() && without_14.is_well_posed());
+        let options_14 = game.verfier(ChosenVerifier(3)).union_of_options();
+        assert!(!without_14
+            .possible_solutions()
+            .into_iter()
+            .all(|code| options_14.contains(code)));
+        assert_eq!(game.redundant_verifiers(), vec![ChosenVerifier(4)]);
+    }
+
+    #[test]
+    fn test_verifier_answer() -> Result<(), crate::code::Error> {
+        // Verifier 11 compares △ with □, with options △ < □, △ = □ and △ > □.
+        let game = Game::new_from_verifier_numbers([11].iter().copied());
+        let verifier = ChosenVerifier(0);
+        let guess = Code::from_digits(2, 4, 1)?;
+        assert_eq!(
+            game.verifier_answer(verifier, 0, guess),
+            VerifierSolution::Check
+        );
+        assert_eq!(
+            game.verifier_answer(verifier, 1, guess),
+            VerifierSolution::Cross
+        );
+        assert_eq!(
+            game.verifier_answer(verifier, 2, guess),
+            VerifierSolution::Cross
+        );
+        let guess = Code::from_digits(3, 3, 1)?;
+        assert_eq!(
+            game.verifier_answer(verifier, 0, guess),
+            VerifierSolution::Cross
+        );
+        assert_eq!(
+            game.verifier_answer(verifier, 1, guess),
+            VerifierSolution::Check
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "the verifier should have an option with this index")]
+    fn test_verifier_answer_invalid_option() {
+        let game = Game::new_from_verifier_numbers([11].iter().copied());
+        let _ = game.verifier_answer(ChosenVerifier(0), 3, Code::from_index(0).unwrap());
+    }
+
+    #[test]
+    fn test_iter_verifiers() {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let (chosen_verifier, verifier) = game.iter_verifiers().next().unwrap();
+        assert_eq!(chosen_verifier, ChosenVerifier::from(0));
+        assert_eq!(verifier.description(), "the □ number compared to 4");
+        assert_eq!(game.iter_verifiers().count(), 4);
+        assert!(game
+            .iter_verifiers()
+            .all(|(chosen_verifier, verifier)| game.verfier(chosen_verifier) == verifier));
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let numbers: Vec<_> = (&game).into_iter().map(Verifier::number).collect();
+        assert_eq!(numbers, vec![Some(4), Some(9), Some(11), Some(14)]);
+        let mut count = 0;
+        for verifier in &game {
+            assert_eq!(verifier, game.verfier(count.into()));
+            count += 1;
+        }
+        assert_eq!(count, 4);
+        assert_eq!(game.clone().into_iter().count(), game.verifier_count());
+    }
+
+    #[test]
+    fn test_verifier_by_letter() {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let (chosen_verifier, verifier) = game.verifier_by_letter('A').unwrap();
+        assert_eq!(chosen_verifier, ChosenVerifier(0));
+        assert_eq!(verifier.number(), Some(4));
+        let (chosen_verifier, verifier) = game.verifier_by_letter('d').unwrap();
+        assert_eq!(chosen_verifier, ChosenVerifier(3));
+        assert_eq!(verifier.number(), Some(14));
+        assert!(game.verifier_by_letter('E').is_none());
+        assert!(game.verifier_by_letter('1').is_none());
+        assert!(game.verifier_by_letter('Ä').is_none());
+    }
+
+    #[test]
+    fn test_verifier_descriptions() {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let descriptions: Vec<_> = game.verifier_descriptions().collect();
+        assert_eq!(descriptions.len(), 4);
+        assert_eq!(
+            descriptions[0],
+            (ChosenVerifier(0), "the □ number compared to 4")
+        );
+        assert_eq!(
+            descriptions[3],
+            (
+                ChosenVerifier(3),
+                "which colour's number is smaller than either of the others"
+            )
+        );
+    }
+
+    #[test]
+    fn test_option_for() {
+        let assignment = Assignment::from_choices([1, 0, 2, 1, 0, 3]);
+        assert_eq!(assignment.option_for(ChosenVerifier(0)), Some(1));
+        assert_eq!(assignment.option_for(ChosenVerifier(2)), Some(2));
+        assert_eq!(assignment.option_for(ChosenVerifier(5)), Some(3));
+        assert_eq!(assignment.option_for(ChosenVerifier(6)), None);
+        assert_eq!(Assignment::default().option_for(ChosenVerifier(0)), None);
+    }
+
+    #[test]
+    fn test_annotated_play_out() -> Result<(), crate::code::Error> {
+        let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+        for secret in game.possible_solutions() {
+            let steps = game.annotated_play_out(secret).unwrap();
+            let last_step = steps.last().unwrap();
+            assert_eq!(last_step.candidates_remaining, 1);
+            assert!(last_step.move_made.is_answer());
+            assert!(steps
+                .windows(2)
+                .all(|pair| pair[0].candidates_remaining >= pair[1].candidates_remaining));
+            let verifiers_checked = steps
+                .iter()
+                .filter(|step| step.move_made.is_verifier_choice())
+                .count();
+            assert!(verifiers_checked <= usize::from(steps[0].best_score.verifiers_checked));
+        }
+        assert_eq!(game.annotated_play_out(Code::from_digits(5, 5, 5)?), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_hardest_secret() {
+        let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+        let (secret, score) = game.hardest_secret().unwrap();
+        assert!(game.possible_solutions().contains(secret));
+        let (best_score, _) = State::new(&game).find_best_move();
+        assert!(score <= best_score);
+        assert_eq!(score.codes_guessed, best_score.codes_guessed);
+
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14, 1].iter().copied());
+        assert_eq!(game.hardest_secret(), None);
+    }
+
+    #[test]
+    fn test_parse_game() {
+        assert_eq!(
+            "3, 7,10 14".parse::<Game>(),
+            Ok(Game::new_from_verifier_numbers(
+                [3, 7, 10, 14].iter().copied()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_game_unknown_verifier() {
+        assert_eq!(
+            "3,7,49,14".parse::<Game>(),
+            Err(GameParseError::UnknownVerifier(49))
+        );
+        assert_eq!(
+            "3,7,x,14".parse::<Game>(),
+            Err(GameParseError::InvalidNumber("x".to_string()))
+        );
+        assert_eq!(
+            "1,2,3,4,5,6,7".parse::<Game>(),
+            Err(GameParseError::InvalidVerifierCount(7))
+        );
+        assert_eq!(
+            "3,7,10,7".parse::<Game>(),
+            Err(GameParseError::DuplicateVerifier(7))
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_possible_solutions_parallel() {
+        let game = Game::new_from_verifier_numbers([12, 16, 18, 19, 21].iter().copied());
+        assert_eq!(
+            game.possible_solutions_parallel(),
+            game.possible_solutions()
+        );
+    }
+
+    #[test]
+    fn test_minimal_sufficient_subsets_redundant_verifier() {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        // Verifier 1 never gives additional information on top of the others.
+        let game_with_redundant_verifier =
+            Game::new_from_verifier_numbers([4, 9, 11, 14, 1].iter().copied());
+        assert!(!game_with_redundant_verifier.is_well_posed());
+
+        let subsets = game_with_redundant_verifier.minimal_sufficient_subsets();
+        assert_eq!(subsets, game.minimal_sufficient_subsets());
+        for subset in subsets {
+            assert!(subset.is_well_posed());
+            assert!(subset.verifier_count() < game.verifier_count());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "a game with 32 or more verifiers does not fit in a mask")]
+    fn test_redundant_verifiers_too_many_verifiers() {
+        let game = Game::new_from_verifier_numbers(std::iter::repeat(4).take(32));
+        let _ = game.redundant_verifiers();
+    }
+
+    use proptest::prelude::*;
+
+    proptest! {
+        // Parsing arbitrary input should never panic.
+        #[test]
+        fn test_parse_arbitrary_string(input in any::<String>()) {
+            let _ = input.parse::<Game>();
+        }
+
+        // Parsing a list of numbers should only succeed for a valid game, and
+        // should otherwise give the first problem that was found.
+        #[test]
+        fn test_parse_arbitrary_numbers(
+            numbers in proptest::collection::vec(0..60usize, 0..8)
+        ) {
+            let input = numbers
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            match input.parse::<Game>() {
+                Ok(game) => {
+                    prop_assert_eq!(
+                        game,
+                        Game::new_from_verifier_numbers(numbers.iter().copied())
+                    );
+                }
+                Err(GameParseError::InvalidVerifierCount(count)) => {
+                    prop_assert_eq!(count, numbers.len());
+                    prop_assert!(!(1..=6).contains(&count));
+                }
+                Err(GameParseError::UnknownVerifier(number)) => {
+                    prop_assert!(numbers.contains(&number));
+                    prop_assert!(!(1..=48).contains(&number));
+                }
+                Err(GameParseError::DuplicateVerifier(number)) => {
+                    prop_assert!(numbers.iter().filter(|n| **n == number).count() > 1);
+                }
+                Err(error @ GameParseError::InvalidNumber(_)) => {
+                    prop_assert!(false, "unexpected error {:?}", error);
+                }
+            }
+        }
+
+        // Any verifier number of a card may be used to construct a game.
+        #[test]
+        fn test_new_from_valid_verifier_numbers(
+            numbers in proptest::collection::vec(1..=48usize, 0..=6)
+        ) {
+            let game = Game::new_from_verifier_numbers(numbers.iter().copied());
+            prop_assert_eq!(game.verifier_count(), numbers.len());
+        }
+    }
 }
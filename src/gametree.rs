@@ -1,20 +1,25 @@
 //! Contains all stateful logic for the game.
-//! 
+//!
 //! This module contains the tools to find the best course of action for
 //! solving a particular game.
 
-use std::fmt::Debug;
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Display, Write},
+    iter::Sum,
+    str::FromStr,
+};
 
 use thiserror::Error;
 
 use crate::{
-    code::{Code, Set},
+    code::{self, Code, Set},
     game::{ChosenVerifier, Game},
     verifier::VerifierOption,
 };
 
 /// A struct representing the current game state.
-/// 
+///
 /// It contains the possible
 /// solutions for the verifier selection, the currently selected code (if any),
 /// the currently selected verifier (if any), whether a verifier was tested, as
@@ -26,6 +31,8 @@ pub struct State<'a> {
     possible_codes: Set,
     current_selection: CodeVerifierChoice,
     has_guessed_one_verifier_for_code: bool,
+    /// The number of verifiers checked for the current code.
+    verifiers_checked_for_code: u8,
     codes_guessed: u8,
     verifiers_checked: u8,
 }
@@ -49,14 +56,151 @@ enum CodeVerifierChoice {
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 struct StateScore(u16);
 
+/// The objective the solver minimizes in the worst case. See
+/// [`State::find_best_move_with_objective`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Hash)]
+pub enum Objective {
+    /// Minimize the number of rounds, i.e. codes guessed, and only then the
+    /// number of verifier checks. This is how the physical game is scored.
+    #[default]
+    FewestRounds,
+    /// Minimize the total number of codes guessed and verifiers checked, and
+    /// only then the number of rounds. Since a round may end after a single
+    /// check, an extra round sometimes saves more than one check.
+    FewestTotal,
+}
+
 /// This represents the current "score" associated with the game state.
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+///
+/// Scores are ordered first by the number of codes guessed, and then by the
+/// number of verifiers checked, in the same way the solver minimizes them.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
 pub struct GameScore {
     pub codes_guessed: u8,
     pub verifiers_checked: u8,
 }
 
+/// A single step of a game played by the solver. See
+/// [`Game::annotated_play_out`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct PlayStep {
+    /// The move that was made.
+    pub move_made: Move,
+    /// The number of codes that are still possible after the move.
+    pub candidates_remaining: u32,
+    /// The worst-case score of the whole game, as predicted by the solver
+    /// when the move was made.
+    pub best_score: GameScore,
+}
+
+impl GameScore {
+    /// Get the number of rounds played, which is the number of codes guessed.
+    #[must_use]
+    pub fn rounds(self) -> u8 {
+        self.codes_guessed
+    }
+
+    /// Combine the score into a single number, where a lower number is a
+    /// better score. The number of codes guessed weighs more than any number
+    /// of verifier checks, so that comparing totals gives the same order as
+    /// comparing the scores themselves.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::gametree::GameScore;
+    ///
+    /// let score = GameScore { codes_guessed: 2, verifiers_checked: 5 };
+    /// assert_eq!(score.total(), 2 * 256 + 5);
+    /// let better = GameScore { codes_guessed: 1, verifiers_checked: 9 };
+    /// assert!(better.total() < score.total());
+    /// ```
+    #[must_use]
+    pub fn total(self) -> u16 {
+        u16::from(self.codes_guessed) << 8 | u16::from(self.verifiers_checked)
+    }
+
+    /// Add the codes guessed and verifiers checked of both scores, for
+    /// example to total the scores of several games. Returns `None` if either
+    /// count overflows.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::gametree::GameScore;
+    ///
+    /// let score = GameScore { codes_guessed: 2, verifiers_checked: 5 };
+    /// let sum = score.checked_add(GameScore { codes_guessed: 1, verifiers_checked: 3 });
+    /// assert_eq!(sum, Some(GameScore { codes_guessed: 3, verifiers_checked: 8 }));
+    /// let large = GameScore { codes_guessed: 1, verifiers_checked: 255 };
+    /// assert_eq!(score.checked_add(large), None);
+    /// ```
+    #[must_use]
+    pub fn checked_add(self, other: GameScore) -> Option<GameScore> {
+        Some(GameScore {
+            codes_guessed: self.codes_guessed.checked_add(other.codes_guessed)?,
+            verifiers_checked: self
+                .verifiers_checked
+                .checked_add(other.verifiers_checked)?,
+        })
+    }
+}
+
+impl Sum for GameScore {
+    /// Total the scores. The counts saturate at [`u8::MAX`] instead of
+    /// overflowing; use [`GameScore::checked_add`] to detect this.
+    fn sum<I: Iterator<Item = GameScore>>(iter: I) -> Self {
+        iter.fold(
+            GameScore {
+                codes_guessed: 0,
+                verifiers_checked: 0,
+            },
+            |total, score| GameScore {
+                codes_guessed: total.codes_guessed.saturating_add(score.codes_guessed),
+                verifiers_checked: total
+                    .verifiers_checked
+                    .saturating_add(score.verifiers_checked),
+            },
+        )
+    }
+}
+
+impl From<GameScore> for (u8, u8) {
+    /// Get the number of codes guessed and the number of verifiers checked.
+    ///
+    /// ```rust
+    /// use turing_machine_ai::gametree::GameScore;
+    ///
+    /// let score = GameScore { codes_guessed: 2, verifiers_checked: 5 };
+    /// assert_eq!(<(u8, u8)>::from(score), (2, 5));
+    /// ```
+    fn from(score: GameScore) -> Self {
+        (score.codes_guessed, score.verifiers_checked)
+    }
+}
+
+impl Display for GameScore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let codes = if self.codes_guessed == 1 {
+            "code"
+        } else {
+            "codes"
+        };
+        let verifiers = if self.verifiers_checked == 1 {
+            "verifier"
+        } else {
+            "verifiers"
+        };
+        write!(
+            f,
+            "{} {codes}, {} {verifiers}",
+            self.codes_guessed, self.verifiers_checked
+        )
+    }
+}
+
 impl StateScore {
+    /// The bit that is set for the score of a lost game.
+    const LOST_BIT: u16 = 1 << 15;
+
     fn no_solution() -> Self {
         // A state without a solution gives the best possible score, which is
         // the worst possible score for the "opponent". This
@@ -69,8 +213,16 @@ impl StateScore {
         StateScore(0)
     }
 
-    fn solution(codes_guessed: u8, verifier_checks: u8) -> Self {
-        StateScore(u16::from(codes_guessed) << 8 | u16::from(verifier_checks))
+    /// Get the score of a solved game for the given objective. The quantity
+    /// that is minimized first is stored in the high byte.
+    fn solution_for(objective: Objective, codes_guessed: u8, verifier_checks: u8) -> Self {
+        let (high, low) = match objective {
+            Objective::FewestRounds => (codes_guessed, verifier_checks),
+            Objective::FewestTotal => {
+                (codes_guessed.saturating_add(verifier_checks), codes_guessed)
+            }
+        };
+        StateScore(u16::from(high) << 8 | u16::from(low))
     }
 
     /// This is represented by the worst possible outcome for the verifier.
@@ -80,18 +232,44 @@ impl StateScore {
         StateScore(u16::MAX)
     }
 
+    /// A wrong declaration of the solution after the given number of codes
+    /// and verifier checks, which loses the game. Lost games are stored with
+    /// the highest bit set. This makes them worse than any game that is won,
+    /// but still better than a useless verifier check, so that guessing is
+    /// preferred over an endless game. Among lost games, those that took fewer
+    /// codes and checks for the given objective are better.
+    fn loss_for(objective: Objective, codes_guessed: u8, verifier_checks: u8) -> Self {
+        let cost = Self::solution_for(objective, codes_guessed, verifier_checks).0;
+        StateScore(Self::LOST_BIT | cost.min(u16::MAX - 1 - Self::LOST_BIT))
+    }
+
     /// Get how many codes and verifiers were checked for this game score. If
-    /// the game did not finish for whatever reason, this function will return
-    /// `None`.
+    /// the game did not finish for whatever reason, or if it was lost, this
+    /// function will return `None`.
     pub fn codes_and_verifiers_checked(self) -> Option<GameScore> {
-        if self.0 == u16::MAX {
-            None
-        } else {
-            Some(GameScore {
-                codes_guessed: (self.0 >> 8) as u8,
-                verifiers_checked: (self.0 & 0b1111_1111) as u8,
-            })
+        self.game_score_for(Objective::FewestRounds)
+    }
+
+    /// Get how many codes and verifiers were checked for a game score of the
+    /// given objective, like [`StateScore::codes_and_verifiers_checked`].
+    fn game_score_for(self, objective: Objective) -> Option<GameScore> {
+        // Both useless verifier checks and lost games have the highest bit
+        // set.
+        if self.0 & Self::LOST_BIT != 0 {
+            return None;
         }
+        let high = (self.0 >> 8) as u8;
+        let low = (self.0 & 0b1111_1111) as u8;
+        Some(match objective {
+            Objective::FewestRounds => GameScore {
+                codes_guessed: high,
+                verifiers_checked: low,
+            },
+            Objective::FewestTotal => GameScore {
+                codes_guessed: low,
+                verifiers_checked: high - low,
+            },
+        })
     }
 
     fn min_score() -> Self {
@@ -103,6 +281,31 @@ impl StateScore {
     }
 }
 
+/// Statistics about a search performed by
+/// [`State::find_best_move_instrumented`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Hash)]
+pub struct SearchStats {
+    /// The number of game states that were visited.
+    pub nodes_visited: u64,
+    /// The number of times the remaining moves of a state were skipped
+    /// because they could not influence the result.
+    pub pruned_branches: u64,
+}
+
+/// The settings and statistics of a single search.
+#[derive(Clone, Eq, PartialEq, Debug, Default, Hash)]
+struct Search {
+    /// Whether the player may declare a solution without being certain.
+    allow_guessing: bool,
+    /// What the player minimizes.
+    objective: Objective,
+    /// The pairs of codes that can not be told apart, see
+    /// [`State::inseparable_pairs`]. They are computed when the search
+    /// starts.
+    inseparable_pairs: Option<Vec<Set>>,
+    stats: SearchStats,
+}
+
 /// Additional info that may be returned by the function `State::after_move`.
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
 pub enum AfterMoveInfo {
@@ -130,6 +333,35 @@ pub enum VerifierSolution {
     Check,
 }
 
+impl TryFrom<char> for VerifierSolution {
+    type Error = VerifierSolutionParseError;
+
+    /// Parse a verifier answer as written down by players: `x` for a cross
+    /// and `v` for a check. Both lower and upper case are accepted.
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'x' | 'X' => Ok(VerifierSolution::Cross),
+            'v' | 'V' => Ok(VerifierSolution::Check),
+            other => Err(VerifierSolutionParseError(other)),
+        }
+    }
+}
+
+impl Display for VerifierSolution {
+    /// Display the verifier answer as `✓` or `✗`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerifierSolution::Check => write!(f, "✓"),
+            VerifierSolution::Cross => write!(f, "✗"),
+        }
+    }
+}
+
+/// An error returned when a character does not represent a verifier answer.
+#[derive(Copy, Clone, Eq, PartialEq, Error, Debug, Hash)]
+#[error("'{0}' is not a verifier answer, expected 'x' or 'v'")]
+pub struct VerifierSolutionParseError(pub char);
+
 /// A move to be taken for a particular game state.
 #[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
 pub enum Move {
@@ -145,6 +377,153 @@ pub enum Move {
     ChooseVerifier(ChosenVerifier),
 }
 
+impl Move {
+    /// Returns whether this move is [`Move::ChooseNewCode`].
+    #[must_use]
+    pub fn is_code_choice(&self) -> bool {
+        matches!(self, Move::ChooseNewCode(_))
+    }
+
+    /// Returns whether this move is [`Move::ChooseVerifier`].
+    #[must_use]
+    pub fn is_verifier_choice(&self) -> bool {
+        matches!(self, Move::ChooseVerifier(_))
+    }
+
+    /// Returns whether this move is [`Move::VerifierSolution`].
+    #[must_use]
+    pub fn is_answer(&self) -> bool {
+        matches!(self, Move::VerifierSolution(_))
+    }
+}
+
+impl Display for Move {
+    /// Format the move in game notation, i.e. `code 351`, `verifier B` or
+    /// `answer check`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Move::ChooseNewCode(code) => {
+                let (triangle, square, circle) = code.digits();
+                write!(f, "code {triangle}{square}{circle}")
+            }
+            Move::ChooseVerifier(verifier) => write!(f, "verifier {verifier}"),
+            Move::VerifierSolution(VerifierSolution::Check) => write!(f, "answer check"),
+            Move::VerifierSolution(VerifierSolution::Cross) => write!(f, "answer cross"),
+        }
+    }
+}
+
+/// A node in the tree of the optimal strategy, as returned by
+/// [`State::strategy_tree`].
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub enum StrategyNode {
+    /// Choose the code, after which the strategy continues with `child`.
+    ChooseCode {
+        code: Code,
+        child: Box<StrategyNode>,
+    },
+    /// Choose the verifier, after which the strategy depends on its answer.
+    /// An answer is `None` if the verifier can not give it.
+    ChooseVerifier {
+        verifier: ChosenVerifier,
+        on_check: Option<Box<StrategyNode>>,
+        on_cross: Option<Box<StrategyNode>>,
+    },
+    /// The code is the solution.
+    Solved { code: Code },
+}
+
+/// An action to be taken by the player, as returned by
+/// [`State::find_best_action`].
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Hash)]
+pub enum Action {
+    /// Play a regular move.
+    Move(Move),
+    /// Declare the code to be the solution of the game.
+    DeclareSolution(Code),
+}
+
+/// An error which may be returned when parsing a [`Move`].
+#[derive(Copy, Clone, Eq, PartialEq, Error, Debug, Hash)]
+pub enum MoveParseError {
+    /// The input was not of the form `code 351`, `verifier B` or
+    /// `answer check`.
+    #[error("expected 'code', 'verifier' or 'answer' followed by a single value")]
+    InvalidFormat,
+    /// The code was not made up of three valid digits.
+    #[error("invalid code")]
+    InvalidCode(#[from] code::Error),
+    /// The verifier was not a letter, optionally followed by a number.
+    #[error("invalid verifier, expected a letter optionally followed by a number")]
+    InvalidVerifier,
+    /// The answer was not `check` or `cross`.
+    #[error("invalid answer, expected 'check' or 'cross'")]
+    InvalidAnswer,
+}
+
+impl FromStr for Move {
+    type Err = MoveParseError;
+
+    /// Parse a move from the notation produced by its [`Display`]
+    /// implementation.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::{code::Code, gametree::{Move, VerifierSolution}};
+    ///
+    /// assert_eq!("code 351".parse(), Ok(Move::ChooseNewCode(Code::from_digits(3, 5, 1)?)));
+    /// assert_eq!("verifier B".parse(), Ok(Move::ChooseVerifier(1.into())));
+    /// assert_eq!("answer cross".parse(), Ok(Move::VerifierSolution(VerifierSolution::Cross)));
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let (Some(kind), Some(value), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(MoveParseError::InvalidFormat);
+        };
+        match kind {
+            "code" => {
+                let digits = value
+                    .chars()
+                    .map(|c| c.to_digit(10).and_then(|digit| u8::try_from(digit).ok()))
+                    .collect::<Option<Vec<u8>>>()
+                    .ok_or(code::Error::InvalidDigits)?;
+                match digits[..] {
+                    [triangle, square, circle] => Ok(Move::ChooseNewCode(Code::from_digits(
+                        triangle, square, circle,
+                    )?)),
+                    _ => Err(code::Error::InvalidDigits.into()),
+                }
+            }
+            "verifier" => {
+                let mut chars = value.chars();
+                let letter = chars
+                    .next()
+                    .filter(char::is_ascii_alphabetic)
+                    .ok_or(MoveParseError::InvalidVerifier)?;
+                let round = match chars.as_str() {
+                    "" => 0,
+                    round => round
+                        .parse::<usize>()
+                        .map_err(|_| MoveParseError::InvalidVerifier)?,
+                };
+                let index = letter.to_ascii_uppercase() as usize - 'A' as usize;
+                let verifier = round
+                    .checked_mul(26)
+                    .and_then(|round| round.checked_add(index))
+                    .ok_or(MoveParseError::InvalidVerifier)?;
+                Ok(Move::ChooseVerifier(verifier.into()))
+            }
+            "answer" => match value {
+                "check" => Ok(Move::VerifierSolution(VerifierSolution::Check)),
+                "cross" => Ok(Move::VerifierSolution(VerifierSolution::Cross)),
+                _ => Err(MoveParseError::InvalidAnswer),
+            },
+            _ => Err(MoveParseError::InvalidFormat),
+        }
+    }
+}
+
 /// An error which may be returned by [`State::after_move`].
 #[derive(Copy, Clone, Eq, PartialEq, Error, Debug, Hash)]
 pub enum AfterMoveError {
@@ -160,14 +539,37 @@ pub enum AfterMoveError {
     NoCodesLeft,
 }
 
+/// An error which may be returned by [`solve_many`] when no first move can be
+/// found for a game.
+#[derive(Copy, Clone, Eq, PartialEq, Error, Debug, Hash)]
+pub enum FindMoveError {
+    /// No code satisfies the verifiers of the game.
+    #[error("the game does not have a solution")]
+    NoSolution,
+    /// The solution is already known without any move.
+    #[error("the game is already solved")]
+    AlreadySolved,
+    /// Several codes are possible, but no verifier can tell them apart. Use
+    /// [`State::find_best_action`] to declare one of them instead.
+    #[error("the possible solutions cannot be told apart")]
+    Indistinguishable,
+}
+
 impl<'a> State<'a> {
     #[must_use]
     pub fn new(game: &'a Game) -> Self {
+        State::with_possible_codes(game, game.possible_solutions())
+    }
+
+    /// Get the initial state for the game, when its possible solutions are
+    /// already known.
+    pub(crate) fn with_possible_codes(game: &'a Game, possible_codes: Set) -> Self {
         State {
             game,
-            possible_codes: game.possible_solutions(),
+            possible_codes,
             current_selection: CodeVerifierChoice::None,
             has_guessed_one_verifier_for_code: false,
+            verifiers_checked_for_code: 0,
             codes_guessed: 0,
             verifiers_checked: 0,
         }
@@ -179,9 +581,46 @@ impl<'a> State<'a> {
         self.possible_codes
     }
 
+    /// Narrow down the possible codes using information obtained outside of
+    /// the verifiers. Only codes that are also contained in the provided set
+    /// remain possible.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::{code::Set, game::Game, gametree::State};
+    ///
+    /// let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+    /// let state = State::new(&game).intersect_with_set(Set::from_closure(|code| code.circle() == 5));
+    /// assert!(state.possible_codes().into_iter().all(|code| code.circle() == 5));
+    /// ```
+    #[must_use]
+    pub fn intersect_with_set(mut self, set: Set) -> Self {
+        self.possible_codes = self.possible_codes.intersected_with(set);
+        self
+    }
+
+    /// Get the indices of the options the given verifier may still have, in
+    /// light of the codes that are still possible.
+    #[must_use]
+    pub fn possible_options(&self, verifier: ChosenVerifier) -> Vec<u8> {
+        self.game.possible_options(verifier, self.possible_codes)
+    }
+
     #[must_use]
     pub fn is_solved(self) -> bool {
-        self.possible_codes.size() == 1
+        self.possible_codes.is_singleton()
+    }
+
+    /// If solved, returns the number of codes guessed and verifiers checked
+    /// to get here. This is the score the solver assigns to a solved state, so
+    /// it can be used to evaluate the leaves of a custom search. Otherwise, it
+    /// returns `None`.
+    #[must_use]
+    pub fn score_if_solved(self) -> Option<GameScore> {
+        self.is_solved().then_some(GameScore {
+            codes_guessed: self.codes_guessed,
+            verifiers_checked: self.verifiers_checked,
+        })
     }
 
     /// If solved, returns the solution. Otherwise, it returns `None`.
@@ -201,11 +640,12 @@ impl<'a> State<'a> {
     /// # Errors
     /// This function returns an [`AfterMoveError`] in one of two cases:
     /// - [`AfterMoveError::InvalidMoveError`] indicates that the provided move
-    ///     was invalid. For example, a verifier was chosen while still waiting
-    ///     on the result of another verifier.
+    ///   was invalid. For example, a verifier was chosen while still waiting
+    ///   on the result of another verifier, or the verifier is not part of
+    ///   the game.
     /// - [`AfterMoveError::NoCodesLeft`] indicates that the game state is
-    ///     invalid. Either the provided game has no solution or one of the
-    ///     verifiers was supplied with the wrong response.
+    ///   invalid. Either the provided game has no solution or one of the
+    ///   verifiers was supplied with the wrong response.
     pub fn after_move(
         mut self,
         move_to_do: Move,
@@ -219,12 +659,17 @@ impl<'a> State<'a> {
                 self.current_selection = CodeVerifierChoice::Code(code);
                 self.codes_guessed += 1;
                 self.has_guessed_one_verifier_for_code = false;
+                self.verifiers_checked_for_code = 0;
             }
             Move::ChooseVerifier(chosen_verifier) => {
+                if !self.game.has_verifier(chosen_verifier) {
+                    return Err(AfterMoveError::InvalidMoveError);
+                }
                 if let CodeVerifierChoice::Code(code) = self.current_selection {
                     self.current_selection =
                         CodeVerifierChoice::CodeAndVerifier(code, chosen_verifier);
                     self.verifiers_checked += 1;
+                    self.verifiers_checked_for_code += 1;
                 } else {
                     return Err(AfterMoveError::InvalidMoveError);
                 }
@@ -233,18 +678,9 @@ impl<'a> State<'a> {
                 if let CodeVerifierChoice::CodeAndVerifier(chosen_code, chosen_verifier) =
                     self.current_selection
                 {
-                    // Get all codes that correspond to a verifier option giving the provided answer.
-                    let bitmask_for_solution = self
-                        .game
-                        .verfier(chosen_verifier)
-                        .options()
-                        .map(VerifierOption::code_set)
-                        .filter(|code_set| {
-                            let would_give_check = code_set.contains(chosen_code);
-                            let gives_check = verifier_solution == VerifierSolution::Check;
-                            would_give_check == gives_check
-                        })
-                        .collect::<Set>();
+                    let bitmask_for_solution =
+                        self.game
+                            .codes_for_answer(chosen_verifier, chosen_code, verifier_solution);
                     let possible_codes = self.possible_codes;
                     let new_possible_codes = possible_codes.intersected_with(bitmask_for_solution);
                     if new_possible_codes == possible_codes {
@@ -255,12 +691,14 @@ impl<'a> State<'a> {
                         self.possible_codes = new_possible_codes;
                     }
 
-                    // If three verifiers were checked, we must select a new
-                    // code. Otherwise, reset just the verifier selection.
-                    if self.verifiers_checked == 3 {
+                    // If three verifiers were checked for this code, we must
+                    // select a new code. Otherwise, reset just the verifier
+                    // selection, after which the round may also end.
+                    if self.verifiers_checked_for_code == 3 {
                         self.current_selection = CodeVerifierChoice::None;
                     } else {
                         self.current_selection = CodeVerifierChoice::Code(chosen_code);
+                        self.has_guessed_one_verifier_for_code = true;
                     }
                 } else {
                     return Err(AfterMoveError::InvalidMoveError);
@@ -270,6 +708,128 @@ impl<'a> State<'a> {
         Ok((self, info))
     }
 
+    /// Returns whether the move may be made in this state, without applying
+    /// it. A new code can be chosen unless a verifier answer is awaited, a
+    /// verifier of the game can only be chosen after a code, and an answer can
+    /// only be given after a verifier. If this returns `false`, [`State::after_move`]
+    /// returns [`AfterMoveError::InvalidMoveError`].
+    ///
+    /// A legal answer may still contradict the earlier answers, in which case
+    /// [`State::after_move`] returns [`AfterMoveError::NoCodesLeft`].
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::{code::Code, game::Game, gametree::{Move, State, VerifierSolution}};
+    ///
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    /// let state = State::new(&game);
+    /// assert!(state.is_legal(Move::ChooseNewCode(Code::from_digits(1, 1, 1)?)));
+    /// assert!(!state.is_legal(Move::ChooseVerifier(0.into())));
+    /// assert!(!state.is_legal(Move::VerifierSolution(VerifierSolution::Check)));
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn is_legal(&self, move_to_do: Move) -> bool {
+        match move_to_do {
+            Move::ChooseNewCode(_) => !self.is_awaiting_result(),
+            Move::ChooseVerifier(verifier) => {
+                self.game.has_verifier(verifier)
+                    && matches!(self.current_selection, CodeVerifierChoice::Code(_))
+            }
+            Move::VerifierSolution(_) => self.is_awaiting_result(),
+        }
+    }
+
+    /// Count the remaining possible codes that are consistent with a check and
+    /// with a cross, respectively, if the verifier were checked against the
+    /// current code. For verifiers with overlapping options, a code may be
+    /// counted for both answers.
+    ///
+    /// # Panics
+    /// This function will panic if no code is selected.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::{code::Code, game::Game, gametree::{Move, State}};
+    ///
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    /// let (state, _) = State::new(&game)
+    ///     .after_move(Move::ChooseNewCode(Code::from_digits(1, 1, 1)?))
+    ///     .unwrap();
+    /// assert_eq!(state.answer_split(0.into()), (1, 1));
+    /// # Ok::<(), turing_machine_ai::code::Error>(())
+    /// ```
+    #[must_use]
+    pub fn answer_split(&self, verifier: ChosenVerifier) -> (u32, u32) {
+        let code = self
+            .current_code()
+            .expect("a code should be selected to split on a verifier answer");
+        let [check, cross] = [VerifierSolution::Check, VerifierSolution::Cross].map(|answer| {
+            self.possible_codes
+                .intersected_with(self.game.codes_for_answer(verifier, code, answer))
+                .size()
+        });
+        (check, cross)
+    }
+
+    /// Apply the moves one by one, starting from the initial state of the
+    /// game, to check whether they are consistent.
+    ///
+    /// # Errors
+    /// If one of the moves cannot be applied, its index is returned together
+    /// with the error. Typically this is [`AfterMoveError::NoCodesLeft`],
+    /// indicating that a verifier answer contradicts the earlier ones.
+    pub fn validate_answers(game: &Game, moves: &[Move]) -> Result<(), (usize, AfterMoveError)> {
+        moves
+            .iter()
+            .enumerate()
+            .try_fold(State::new(game), |state, (index, move_to_do)| {
+                state
+                    .after_move(*move_to_do)
+                    .map(|(state, _)| state)
+                    .map_err(|error| (index, error))
+            })
+            .map(|_| ())
+    }
+
+    /// Estimate how much information testing the given code provides, without
+    /// performing a full search. For every verifier, the remaining codes are
+    /// split into those that are consistent with a check and those that are
+    /// consistent with a cross. The result is the sum of the entropies of
+    /// these splits, in bits. A higher value means the code is more likely to
+    /// narrow down the possible codes quickly.
+    ///
+    /// For verifiers with overlapping options, a code may be consistent with
+    /// both answers, in which case it is counted for both.
+    #[must_use]
+    pub fn expected_split_for_code(self, code: Code) -> f64 {
+        self.game
+            .iter_verifier_choices()
+            .map(|chosen_verifier| {
+                let (check, cross) = self
+                    .game
+                    .verfier(chosen_verifier)
+                    .options()
+                    .map(VerifierOption::code_set)
+                    .fold((Set::empty(), Set::empty()), |(check, cross), code_set| {
+                        if code_set.contains(code) {
+                            (check.union_with(code_set), cross)
+                        } else {
+                            (check, cross.union_with(code_set))
+                        }
+                    });
+                let check = f64::from(self.possible_codes.intersected_with(check).size());
+                let cross = f64::from(self.possible_codes.intersected_with(cross).size());
+                let total = check + cross;
+                [check, cross]
+                    .into_iter()
+                    .filter(|count| *count > 0.0)
+                    .map(|count| -(count / total) * (count / total).log2())
+                    .sum::<f64>()
+            })
+            .sum()
+    }
+
     /// Returns true if the game is awaiting a verifier answer.
     #[must_use]
     pub fn is_awaiting_result(&self) -> bool {
@@ -288,13 +848,47 @@ impl<'a> State<'a> {
         )
     }
 
+    /// Get the code that is currently being tested, if any.
+    #[must_use]
+    pub fn current_code(&self) -> Option<Code> {
+        match self.current_selection {
+            CodeVerifierChoice::None => None,
+            CodeVerifierChoice::Code(code) | CodeVerifierChoice::CodeAndVerifier(code, _) => {
+                Some(code)
+            }
+        }
+    }
+
+    /// Get the verifier whose answer the game is awaiting, if any.
+    #[must_use]
+    pub fn awaiting_verifier(&self) -> Option<ChosenVerifier> {
+        if let CodeVerifierChoice::CodeAndVerifier(_, verifier) = self.current_selection {
+            Some(verifier)
+        } else {
+            None
+        }
+    }
+
+    /// Get how many verifiers were chosen for the code that is currently
+    /// being tested. If no code is selected, this returns 0.
+    #[must_use]
+    pub fn verifiers_checked_for_current_code(&self) -> u8 {
+        if self.has_selected_code() {
+            self.verifiers_checked_for_code
+        } else {
+            0
+        }
+    }
+
     /// Return all possible moves. Notably these are not verified in every way:
     /// - Verifiers may return impossible results, leading to no solution.
     /// - Codes or verifiers may be chosen that do not provide information to
     ///   the player.
+    ///
+    /// Once a single code remains the game is solved, so there are no moves,
+    /// even if a code or verifier is still selected.
     pub fn possible_moves(&self) -> impl Iterator<Item = Move> + '_ {
         // This function looks messy to avoid allocating a Vec with moves.
-
         // If awaiting result, return both results
         [
             Move::VerifierSolution(VerifierSolution::Check),
@@ -312,12 +906,21 @@ impl<'a> State<'a> {
                 .filter(|_| self.has_selected_code())
                 .chain(
                     // If the code was used once, or if no code was selected, choose new code
-                    Set::all().into_iter().map(Move::ChooseNewCode).filter(|_| {
-                        !self.has_selected_code() || self.has_guessed_one_verifier_for_code
-                    }),
+                    Set::all()
+                        .into_iter()
+                        .map(Move::ChooseNewCode)
+                        .filter(|_| self.may_choose_new_code()),
                 )
                 .filter(|_| !self.is_awaiting_result()),
         )
+        // No moves are possible once the game is solved.
+        .filter(|_| !self.is_solved())
+    }
+
+    /// Returns whether the player may end the round by choosing a new code,
+    /// i.e. whether no code was selected or it was used for a verifier.
+    fn may_choose_new_code(self) -> bool {
+        !self.has_selected_code() || self.has_guessed_one_verifier_for_code
     }
 
     /// Returns whether the state demands maximizing the score. This
@@ -327,55 +930,198 @@ impl<'a> State<'a> {
         !self.is_awaiting_result()
     }
 
+    /// Get the score of the state after performing the given move. If the
+    /// game continues, `score_state` is used to score the resulting state.
+    fn score_after_move(
+        self,
+        move_to_do: Move,
+        score_state: impl FnOnce(State<'a>) -> StateScore,
+    ) -> StateScore {
+        match self.after_move(move_to_do) {
+            Err(AfterMoveError::NoCodesLeft) => StateScore::no_solution(),
+            Err(AfterMoveError::InvalidMoveError) => panic!("invalid move"),
+            Ok((_, Some(AfterMoveInfo::UselessVerifierCheck))) => {
+                StateScore::useless_verifier_check()
+            }
+            Ok((state, None)) => score_state(state),
+        }
+    }
+
+    /// Get the score for the given objective after performing the given
+    /// action. If the game continues, `score_state` is used to score the
+    /// resulting state.
+    fn score_after_action(
+        self,
+        action: Action,
+        objective: Objective,
+        score_state: impl FnOnce(State<'a>) -> StateScore,
+    ) -> StateScore {
+        match action {
+            Action::Move(move_to_do) => self.score_after_move(move_to_do, score_state),
+            // Declarations are only made while several codes are possible,
+            // so in the worst case the declaration is wrong and the game is
+            // lost.
+            Action::DeclareSolution(_) => {
+                StateScore::loss_for(objective, self.codes_guessed, self.verifiers_checked)
+            }
+        }
+    }
+
+    /// Get the best score the game may still reach from this unsolved state.
+    /// Solving it takes at least one more verifier check, and a new code if
+    /// none is selected. An awaited answer may solve it right away. If two of
+    /// the possible codes can not be told apart, the game can not be won, and
+    /// at best a declaration loses it right away.
+    fn best_possible_score(self, search: &mut Search) -> StateScore {
+        let is_indistinguishable = search
+            .inseparable_pairs
+            .get_or_insert_with(|| self.inseparable_pairs())
+            .iter()
+            .any(|pair| self.possible_codes.intersected_with(*pair) == *pair);
+        if is_indistinguishable {
+            return if search.allow_guessing {
+                StateScore::loss_for(search.objective, self.codes_guessed, self.verifiers_checked)
+            } else {
+                StateScore::useless_verifier_check()
+            };
+        }
+        let (codes_guessed, verifiers_checked) = if self.is_awaiting_result() {
+            (self.codes_guessed, self.verifiers_checked)
+        } else if self.has_selected_code() {
+            (self.codes_guessed, self.verifiers_checked.saturating_add(1))
+        } else {
+            (
+                self.codes_guessed.saturating_add(1),
+                self.verifiers_checked.saturating_add(1),
+            )
+        };
+        StateScore::solution_for(search.objective, codes_guessed, verifiers_checked)
+    }
+
+    /// Get the pairs of possible codes that can not be told apart. For every
+    /// code and verifier, there is an answer that is consistent with both
+    /// codes, so a state containing such a pair can never be solved.
+    fn inseparable_pairs(self) -> Vec<Set> {
+        let answer_sets: Vec<[Set; 2]> = self
+            .game
+            .iter_verifier_choices()
+            .flat_map(|verifier| {
+                Set::all().into_iter().map(move |code| {
+                    [VerifierSolution::Check, VerifierSolution::Cross]
+                        .map(|answer| self.game.codes_for_answer(verifier, code, answer))
+                })
+            })
+            .collect();
+        let codes: Vec<Code> = self.possible_codes.into_iter().collect();
+        codes
+            .iter()
+            .enumerate()
+            .flat_map(|(index, first)| {
+                codes[index + 1..]
+                    .iter()
+                    .map(move |second| Set::from_iter([*first, *second]))
+            })
+            .filter(|pair| {
+                answer_sets.iter().all(|sets| {
+                    sets.iter()
+                        .any(|codes| codes.intersected_with(*pair) == *pair)
+                })
+            })
+            .collect()
+    }
+
+    /// Get the possible codes that remain for every verifier answer, if the
+    /// code were tested. Codes with the same split lead to the same game, so
+    /// only one of them has to be searched.
+    fn code_split(self, code: Code) -> Vec<Set> {
+        self.game
+            .iter_verifier_choices()
+            .flat_map(|verifier| {
+                [VerifierSolution::Check, VerifierSolution::Cross].map(|answer| {
+                    self.possible_codes
+                        .intersected_with(self.game.codes_for_answer(verifier, code, answer))
+                })
+            })
+            .collect()
+    }
+
+    /// Return the possible moves that have to be searched. A new code is
+    /// skipped if an earlier code splits the possible codes in the same way.
+    fn search_moves(&self) -> impl Iterator<Item = Move> + '_ {
+        let mut code_splits = HashSet::new();
+        self.possible_moves()
+            .filter(move |move_to_do| match move_to_do {
+                Move::ChooseNewCode(code) => code_splits.insert(self.code_split(*code)),
+                _ => true,
+            })
+    }
+
     /// Perform minmax with alpha-beta pruning.
-    fn alphabeta(self, mut alpha: StateScore, mut beta: StateScore) -> (StateScore, Option<Move>) {
+    fn alphabeta(
+        self,
+        mut alpha: StateScore,
+        mut beta: StateScore,
+        search: &mut Search,
+    ) -> (StateScore, Option<Action>) {
+        search.stats.nodes_visited += 1;
         // If the game is solved, return the result.
-        if self.is_solved() {
-            (
-                StateScore::solution(self.codes_guessed, self.verifiers_checked),
+        if let Some(score) = self.score_if_solved() {
+            return (
+                StateScore::solution_for(
+                    search.objective,
+                    score.codes_guessed,
+                    score.verifiers_checked,
+                ),
                 None,
-            )
-        } else if self.is_maximizing_score() {
+            );
+        }
+        // If even the best possible outcome is no better than alpha, this
+        // state cannot influence the result.
+        let best_possible_score = self.best_possible_score(search);
+        if best_possible_score <= alpha {
+            search.stats.pruned_branches += 1;
+            return (best_possible_score, None);
+        }
+        if self.is_maximizing_score() {
             let mut highest_score = StateScore::min_score();
-            let mut best_move = None;
-            for move_to_do in self.possible_moves() {
-                let next_node = self.after_move(move_to_do);
-                let score = match next_node {
-                    Err(AfterMoveError::NoCodesLeft) => StateScore::no_solution(),
-                    Err(AfterMoveError::InvalidMoveError) => panic!("invalid move!"),
-                    Ok((_, Some(AfterMoveInfo::UselessVerifierCheck))) => {
-                        StateScore::useless_verifier_check()
-                    }
-                    Ok((state, None)) => state.alphabeta(alpha, beta).0,
-                };
+            let mut best_action = None;
+            // Declarations are only considered when the round may end.
+            let may_declare = search.allow_guessing && self.may_choose_new_code();
+            let declarations = self
+                .possible_codes
+                .into_iter()
+                .filter(|_| may_declare)
+                .map(Action::DeclareSolution);
+            let objective = search.objective;
+            // Declarations are tried first, so that once the game can only be
+            // lost, the other actions are skipped.
+            for action in declarations.chain(self.search_moves().map(Action::Move)) {
+                let score = self.score_after_action(action, objective, |state| {
+                    state.alphabeta(alpha, beta, search).0
+                });
                 if score > highest_score {
                     highest_score = score;
-                    best_move = Some(move_to_do);
+                    best_action = Some(action);
                 }
-                if score > beta {
+                if score >= beta {
+                    search.stats.pruned_branches += 1;
                     break;
                 }
                 if score > alpha {
                     alpha = score;
                 }
             }
-            (highest_score, best_move)
+            (highest_score, best_action)
         } else {
             let mut lowest_score = StateScore::max_score();
-            for move_to_do in self.possible_moves() {
-                let next_node = self.after_move(move_to_do);
-                let score = match next_node {
-                    Err(AfterMoveError::NoCodesLeft) => StateScore::no_solution(),
-                    Err(AfterMoveError::InvalidMoveError) => panic!("invalid move"),
-                    Ok((_, Some(AfterMoveInfo::UselessVerifierCheck))) => {
-                        StateScore::useless_verifier_check()
-                    }
-                    Ok((state, None)) => state.alphabeta(alpha, beta).0,
-                };
+            for move_to_do in self.search_moves() {
+                let score = self
+                    .score_after_move(move_to_do, |state| state.alphabeta(alpha, beta, search).0);
                 if score < lowest_score {
                     lowest_score = score;
                 }
-                if score < alpha {
+                if score <= alpha {
+                    search.stats.pruned_branches += 1;
                     break;
                 }
                 if score < beta {
@@ -387,39 +1133,407 @@ impl<'a> State<'a> {
         }
     }
 
+    /// Perform a full minmax search without any pruning. Unlike
+    /// [`State::alphabeta`], it tries every move of
+    /// [`State::possible_moves`], including new codes that split the possible
+    /// codes like an earlier one. This is much slower, but serves as a
+    /// reference to test it against.
+    #[cfg(test)]
+    fn minimax_reference(self) -> (StateScore, Option<Move>) {
+        if self.is_solved() {
+            return (
+                StateScore::solution_for(
+                    Objective::FewestRounds,
+                    self.codes_guessed,
+                    self.verifiers_checked,
+                ),
+                None,
+            );
+        }
+        let scored_moves = self.possible_moves().map(|move_to_do| {
+            let score = self.score_after_move(move_to_do, |state| state.minimax_reference().0);
+            (score, move_to_do)
+        });
+        if self.is_maximizing_score() {
+            scored_moves.fold(
+                (StateScore::min_score(), None),
+                |(highest_score, best_move), (score, move_to_do)| {
+                    if score > highest_score {
+                        (score, Some(move_to_do))
+                    } else {
+                        (highest_score, best_move)
+                    }
+                },
+            )
+        } else {
+            let lowest_score = scored_moves
+                .map(|(score, _)| score)
+                .fold(StateScore::max_score(), std::cmp::min);
+            (lowest_score, None)
+        }
+    }
+
     /// Find the best possible move to minimize the maximum amount of codes and
     /// verifier checks needed. The game must be at a state where the player
     /// chooses a code or a verifier.
     ///
+    /// Like in the physical game, the number of rounds, i.e. codes guessed, is
+    /// minimized first. Only among the moves needing the fewest rounds is the
+    /// number of verifier checks minimized.
+    ///
     /// # Panics
     /// This function will panic if the state is currently awaiting a verifier
-    /// answer or if the game has already been solved.
+    /// answer, if the game has already been solved, or if no verifier can
+    /// tell the possible codes apart.
     #[must_use]
     pub fn find_best_move(self) -> (GameScore, Move) {
+        let (score, move_to_do, _) = self.find_best_move_instrumented();
+        (score, move_to_do)
+    }
+
+    /// Find the best possible move like [`State::find_best_move`], but
+    /// minimize the given objective instead. With
+    /// [`Objective::FewestRounds`], this is the same as
+    /// [`State::find_best_move`].
+    ///
+    /// # Panics
+    /// This function will panic if the state is currently awaiting a verifier
+    /// answer or if the game has already been solved.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::{game::Game, gametree::{GameScore, Objective, State}};
+    ///
+    /// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    /// let (score, _) = State::new(&game).find_best_move_with_objective(Objective::FewestTotal);
+    /// assert_eq!(score, GameScore { codes_guessed: 1, verifiers_checked: 1 });
+    /// ```
+    #[must_use]
+    pub fn find_best_move_with_objective(self, objective: Objective) -> (GameScore, Move) {
+        let (score, action, _) = self.search_best_action(Search {
+            objective,
+            ..Search::default()
+        });
+        if let Action::Move(move_to_do) = action {
+            (
+                score.expect("the game cannot be lost without declaring a solution"),
+                move_to_do,
+            )
+        } else {
+            unreachable!("declaring a solution is not allowed");
+        }
+    }
+
+    /// Find the best possible move like [`State::find_best_move`], but also
+    /// return statistics about the search that was performed.
+    ///
+    /// # Panics
+    /// This function will panic if the state is currently awaiting a verifier
+    /// answer, if the game has already been solved, or if no verifier can
+    /// tell the possible codes apart.
+    #[must_use]
+    pub fn find_best_move_instrumented(self) -> (GameScore, Move, SearchStats) {
+        let (score, action, stats) = self.search_best_action(Search::default());
+        if let Action::Move(move_to_do) = action {
+            (
+                score.expect("the game cannot be lost without declaring a solution"),
+                move_to_do,
+                stats,
+            )
+        } else {
+            unreachable!("declaring a solution is not allowed");
+        }
+    }
+
+    /// Find the best possible action. If `allow_guessing` is `true`, the
+    /// player may also declare one of the possible codes to be the solution
+    /// whenever the round may end, instead of deducing it with certainty.
+    ///
+    /// A correct declaration wins the game immediately, while a wrong one
+    /// loses it. Since the search assumes the worst case, a declaration is
+    /// only chosen when no strategy is certain to find the solution, for
+    /// example when some of the remaining codes cannot be told apart by any
+    /// verifier. In that case the score is `None`, indicating that the game
+    /// may be lost. Since spending more codes and checks does not avoid the
+    /// loss, a declaration is then made as early as possible.
+    ///
+    /// # Panics
+    /// This function will panic if the state is currently awaiting a verifier
+    /// answer or if the game has already been solved.
+    #[must_use]
+    pub fn find_best_action(self, allow_guessing: bool) -> (Option<GameScore>, Action) {
+        let (score, action, _) = self.search_best_action(Search {
+            allow_guessing,
+            ..Search::default()
+        });
+        (score, action)
+    }
+
+    fn search_best_action(self, mut search: Search) -> (Option<GameScore>, Action, SearchStats) {
         assert!(!self.is_awaiting_result() && !self.is_solved());
         // The optimal possible game.
         let alpha = StateScore::min_score();
         // The worst possible game.
         let beta = StateScore::max_score();
-        if let (score, Some(move_to_do)) = self.alphabeta(alpha, beta) {
-            (score.codes_and_verifiers_checked().unwrap(), move_to_do)
+        if let (score, Some(action)) = self.alphabeta(alpha, beta, &mut search) {
+            (score.game_score_for(search.objective), action, search.stats)
         } else {
             panic!("No move possible");
         }
     }
+
+    /// Export the optimal strategy from this state as a Graphviz DOT graph.
+    ///
+    /// Every node represents a state in which the player makes a move, and is
+    /// labelled with that move and the number of possible codes. After
+    /// choosing a verifier, the graph branches on the possible answers, and
+    /// the edges are labelled `check` or `cross`. The graph ends in nodes
+    /// containing the solution.
+    ///
+    /// # Panics
+    /// This function will panic if the state is currently awaiting a verifier
+    /// answer, or if the game has no solution.
+    #[must_use]
+    pub fn to_dot(self) -> String {
+        assert!(!self.is_awaiting_result());
+        let mut dot = String::from("digraph strategy {\n");
+        self.write_dot_node(&mut dot, &mut 0, 0);
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Write the node for this state and all the states following it, and
+    /// return the identifier of the node.
+    fn write_dot_node(self, dot: &mut String, next_id: &mut usize, depth: usize) -> usize {
+        // Every move narrows down the possible codes or leads up to a verifier
+        // check, so this depth is never reached in practice.
+        const MAX_DEPTH: usize = 64;
+        let id = *next_id;
+        *next_id += 1;
+        if let Some(solution) = self.solution() {
+            let (triangle, square, circle) = solution.digits();
+            writeln!(
+                dot,
+                "    {id} [label=\"solution {triangle}{square}{circle}\"];"
+            )
+            .unwrap();
+            return id;
+        }
+        if depth >= MAX_DEPTH {
+            writeln!(dot, "    {id} [label=\"...\"];").unwrap();
+            return id;
+        }
+        let (_, move_to_do) = self.find_best_move();
+        writeln!(
+            dot,
+            "    {id} [label=\"{move_to_do}\\n{} codes\"];",
+            self.possible_codes.size()
+        )
+        .unwrap();
+        let (next_state, _) = self
+            .after_move(move_to_do)
+            .expect("the best move should be valid");
+        if next_state.is_awaiting_result() {
+            for (answer, label) in [
+                (VerifierSolution::Check, "check"),
+                (VerifierSolution::Cross, "cross"),
+            ] {
+                if let Ok((state, _)) = next_state.after_move(Move::VerifierSolution(answer)) {
+                    let child = state.write_dot_node(dot, next_id, depth + 1);
+                    writeln!(dot, "    {id} -> {child} [label=\"{label}\"];").unwrap();
+                }
+            }
+        } else {
+            let child = next_state.write_dot_node(dot, next_id, depth + 1);
+            writeln!(dot, "    {id} -> {child};").unwrap();
+        }
+        id
+    }
+
+    /// Get the complete optimal strategy from this state, branching on every
+    /// possible verifier answer. Every branch ends in
+    /// [`StrategyNode::Solved`].
+    ///
+    /// # Panics
+    /// This function will panic if the state is currently awaiting a verifier
+    /// answer, or if the game has no solution.
+    #[must_use]
+    pub fn strategy_tree(self) -> StrategyNode {
+        self.strategy_tree_with_objective(Objective::FewestRounds)
+    }
+
+    /// Get the complete optimal strategy like [`State::strategy_tree`], but
+    /// minimize the given objective instead. See
+    /// [`State::find_best_move_with_objective`].
+    ///
+    /// # Panics
+    /// This function will panic if the state is currently awaiting a verifier
+    /// answer, or if the game has no solution.
+    #[must_use]
+    pub fn strategy_tree_with_objective(self, objective: Objective) -> StrategyNode {
+        if let Some(code) = self.solution() {
+            return StrategyNode::Solved { code };
+        }
+        let (_, move_to_do) = self.find_best_move_with_objective(objective);
+        let (next_state, _) = self
+            .after_move(move_to_do)
+            .expect("the best move should be valid");
+        match move_to_do {
+            Move::ChooseNewCode(code) => StrategyNode::ChooseCode {
+                code,
+                child: Box::new(next_state.strategy_tree_with_objective(objective)),
+            },
+            Move::ChooseVerifier(verifier) => {
+                let branch = |answer| {
+                    next_state
+                        .after_move(Move::VerifierSolution(answer))
+                        .ok()
+                        .map(|(state, _)| Box::new(state.strategy_tree_with_objective(objective)))
+                };
+                StrategyNode::ChooseVerifier {
+                    verifier,
+                    on_check: branch(VerifierSolution::Check),
+                    on_cross: branch(VerifierSolution::Cross),
+                }
+            }
+            Move::VerifierSolution(_) => unreachable!("the player never answers a verifier"),
+        }
+    }
+
+    /// Find all moves that are equally good as the best possible move. See
+    /// [`State::find_best_move`]. Since every move has to be scored exactly,
+    /// this is slower than finding a single best move.
+    ///
+    /// # Panics
+    /// This function will panic if the state is currently awaiting a verifier
+    /// answer, if the game has already been solved, or if no verifier can
+    /// tell the possible codes apart.
+    #[must_use]
+    pub fn find_best_moves(self) -> (GameScore, Vec<Move>) {
+        assert!(!self.is_awaiting_result() && !self.is_solved());
+        let mut search = Search::default();
+        let (best_score, _) = self.alphabeta(
+            StateScore::min_score(),
+            StateScore::max_score(),
+            &mut search,
+        );
+        let best_moves = self
+            .possible_moves()
+            .filter(|move_to_do| {
+                self.score_after_move(*move_to_do, |state| {
+                    state
+                        .alphabeta(
+                            StateScore::min_score(),
+                            StateScore::max_score(),
+                            &mut search,
+                        )
+                        .0
+                }) == best_score
+            })
+            .collect();
+        (
+            best_score.codes_and_verifiers_checked().unwrap(),
+            best_moves,
+        )
+    }
+}
+
+/// Find the best first move for a single game.
+fn solve(game: &Game) -> Result<(GameScore, Move), FindMoveError> {
+    let state = State::new(game);
+    if state.possible_codes().size() == 0 {
+        Err(FindMoveError::NoSolution)
+    } else if state.is_solved() {
+        Err(FindMoveError::AlreadySolved)
+    } else {
+        let (score, action) = state.alphabeta(
+            StateScore::min_score(),
+            StateScore::max_score(),
+            &mut Search::default(),
+        );
+        match (score.codes_and_verifiers_checked(), action) {
+            (Some(score), Some(Action::Move(move_to_do))) => Ok((score, move_to_do)),
+            _ => Err(FindMoveError::Indistinguishable),
+        }
+    }
+}
+
+/// Find the score and best first move for each of the given games. The
+/// results are in the same order as the games. A game for which no first move
+/// can be found results in a [`FindMoveError`].
+#[must_use]
+pub fn solve_many(games: &[Game]) -> Vec<Result<(GameScore, Move), FindMoveError>> {
+    games.iter().map(solve).collect()
+}
+
+/// Solve the games like [`solve_many`], but distribute them over multiple
+/// threads. The results are in the same order as the games.
+#[cfg(feature = "parallel")]
+#[must_use]
+pub fn solve_many_parallel(games: &[Game]) -> Vec<Result<(GameScore, Move), FindMoveError>> {
+    use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+    games.par_iter().map(solve).collect()
+}
+
+/// Strategy trees are serialized with codes as a list of their digits, in the
+/// same way as [`Code`] itself, and verifiers as their letter.
+#[cfg(feature = "serde")]
+impl serde::Serialize for StrategyNode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStructVariant;
+        match self {
+            StrategyNode::ChooseCode { code, child } => {
+                let mut variant =
+                    serializer.serialize_struct_variant("StrategyNode", 0, "ChooseCode", 2)?;
+                variant.serialize_field("code", code)?;
+                variant.serialize_field("child", child)?;
+                variant.end()
+            }
+            StrategyNode::ChooseVerifier {
+                verifier,
+                on_check,
+                on_cross,
+            } => {
+                let mut variant =
+                    serializer.serialize_struct_variant("StrategyNode", 1, "ChooseVerifier", 3)?;
+                variant.serialize_field("verifier", &verifier.to_string())?;
+                variant.serialize_field("on_check", on_check)?;
+                variant.serialize_field("on_cross", on_cross)?;
+                variant.end()
+            }
+            StrategyNode::Solved { code } => {
+                let mut variant =
+                    serializer.serialize_struct_variant("StrategyNode", 2, "Solved", 1)?;
+                variant.serialize_field("code", code)?;
+                variant.end()
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::gametree::GameScore;
+    use crate::{
+        code::{self, Code, Set},
+        game::{ChosenVerifier, Game},
+        gametree::GameScore,
+    };
 
-    use super::StateScore;
+    use super::{
+        solve_many, Action, AfterMoveError, CodeVerifierChoice, FindMoveError, Move,
+        MoveParseError, Objective, Search, State, StateScore, StrategyNode, VerifierSolution,
+        VerifierSolutionParseError,
+    };
 
     #[test]
     fn test_game_score() {
         for codes_guessed in 0..10 {
             for verifiers_checked in codes_guessed..30 {
-                let state_score = StateScore::solution(codes_guessed, verifiers_checked);
+                let state_score = StateScore::solution_for(
+                    Objective::FewestRounds,
+                    codes_guessed,
+                    verifiers_checked,
+                );
                 assert_eq!(
                     state_score.codes_and_verifiers_checked().unwrap(),
                     GameScore {
@@ -430,4 +1544,768 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_game_score_ordering() {
+        let score = |codes_guessed, verifiers_checked| GameScore {
+            codes_guessed,
+            verifiers_checked,
+        };
+        assert!(score(1, 3) < score(2, 2));
+        assert!(score(2, 2) < score(2, 3));
+        let mut scores = vec![score(2, 3), score(1, 3), score(2, 2)];
+        scores.sort();
+        assert_eq!(scores, vec![score(1, 3), score(2, 2), score(2, 3)]);
+    }
+
+    #[test]
+    fn test_game_score_display() {
+        let score = GameScore {
+            codes_guessed: 2,
+            verifiers_checked: 4,
+        };
+        assert_eq!(score.to_string(), "2 codes, 4 verifiers");
+        let score = GameScore {
+            codes_guessed: 1,
+            verifiers_checked: 1,
+        };
+        assert_eq!(score.to_string(), "1 code, 1 verifier");
+    }
+
+    #[test]
+    fn test_intersect_with_set() {
+        let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+        let state = State::new(&game);
+        let low_sum = Set::from_closure(|code| code.digit_sum() < 6);
+        let narrowed = state.intersect_with_set(low_sum);
+        assert!(narrowed.possible_codes().size() < state.possible_codes().size());
+        assert_eq!(
+            narrowed.possible_codes(),
+            state.possible_codes().intersected_with(low_sum)
+        );
+    }
+
+    fn booklet_games() -> Vec<Game> {
+        [[4, 9, 11, 14], [3, 7, 10, 14]]
+            .into_iter()
+            .map(|numbers| Game::new_from_verifier_numbers(numbers.into_iter()))
+            .collect()
+    }
+
+    #[test]
+    fn test_solve_many() -> Result<(), Box<dyn std::error::Error>> {
+        let mut games = booklet_games();
+        games.push(Game::new_from_verifier_numbers([4, 9, 11].into_iter()));
+        // The three possible codes can only be told apart using two codes.
+        games.push("3 24".parse()?);
+        // No verifier can tell the two possible codes apart.
+        games.push("20 36".parse()?);
+        // A verifier is redundant with itself, so there is no solution.
+        games.push(Game::new_from_verifier_numbers([4, 4].into_iter()));
+        let score = |codes_guessed, verifiers_checked| GameScore {
+            codes_guessed,
+            verifiers_checked,
+        };
+        assert_eq!(
+            solve_many(&games),
+            vec![
+                Ok((
+                    score(1, 1),
+                    Move::ChooseNewCode(Code::from_digits(1, 1, 1)?)
+                )),
+                Ok((
+                    score(2, 3),
+                    Move::ChooseNewCode(Code::from_digits(1, 1, 1)?)
+                )),
+                Ok((
+                    score(1, 2),
+                    Move::ChooseNewCode(Code::from_digits(4, 4, 1)?)
+                )),
+                Ok((
+                    score(2, 2),
+                    Move::ChooseNewCode(Code::from_digits(1, 1, 1)?)
+                )),
+                Err(FindMoveError::Indistinguishable),
+                Err(FindMoveError::NoSolution),
+            ]
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_solve_many_parallel() {
+        let games = booklet_games();
+        assert_eq!(super::solve_many_parallel(&games), solve_many(&games));
+    }
+
+    #[test]
+    fn test_rounds_dominate_score() {
+        // A single extra round is worse than any number of extra checks.
+        assert!(
+            StateScore::solution_for(Objective::FewestRounds, 1, 9)
+                > StateScore::solution_for(Objective::FewestRounds, 2, 2)
+        );
+        assert!(
+            StateScore::solution_for(Objective::FewestRounds, 2, 2)
+                > StateScore::solution_for(Objective::FewestRounds, 2, 3)
+        );
+        assert!(
+            GameScore {
+                codes_guessed: 1,
+                verifiers_checked: 9
+            } < GameScore {
+                codes_guessed: 2,
+                verifiers_checked: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_objectives_order_scores_differently() {
+        // Many checks for a single code against an extra code with few checks.
+        let two_rounds = |objective| StateScore::solution_for(objective, 2, 6);
+        let three_rounds = |objective| StateScore::solution_for(objective, 3, 3);
+        assert!(two_rounds(Objective::FewestRounds) > three_rounds(Objective::FewestRounds));
+        assert!(two_rounds(Objective::FewestTotal) < three_rounds(Objective::FewestTotal));
+        for objective in [Objective::FewestRounds, Objective::FewestTotal] {
+            assert_eq!(
+                two_rounds(objective).game_score_for(objective),
+                Some(GameScore {
+                    codes_guessed: 2,
+                    verifiers_checked: 6
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_objectives_choose_different_moves() -> Result<(), Box<dyn std::error::Error>> {
+        let game = Game::new_from_verifier_numbers([1, 23, 44].iter().copied());
+        let mut state = State::new(&game);
+        for move_to_do in ["code 331", "verifier B", "answer cross"] {
+            state = state.after_move(move_to_do.parse()?)?.0;
+        }
+        // Checking more verifiers for the current code takes fewer rounds,
+        // while a new code after every check takes fewer checks in total.
+        assert_eq!(
+            state.find_best_move(),
+            (
+                GameScore {
+                    codes_guessed: 2,
+                    verifiers_checked: 5
+                },
+                Move::ChooseVerifier(0.into())
+            )
+        );
+        assert_eq!(
+            state.find_best_move_with_objective(Objective::FewestTotal),
+            (
+                GameScore {
+                    codes_guessed: 3,
+                    verifiers_checked: 3
+                },
+                Move::ChooseNewCode(Code::from_digits(1, 1, 1)?)
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_moves_when_solved() -> Result<(), Box<dyn std::error::Error>> {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let (state, _) = State::new(&game)
+            .after_move(Move::ChooseNewCode(Code::from_digits(1, 1, 1)?))?
+            .0
+            .after_move(Move::ChooseVerifier(0.into()))?
+            .0
+            .after_move(Move::VerifierSolution(VerifierSolution::Check))?;
+        // The code is still selected, but only one candidate is left.
+        assert!(state.has_selected_code());
+        assert_eq!(state.possible_moves().next(), None);
+        assert_eq!(state.solution(), Some(Code::from_digits(2, 2, 1)?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_score_if_solved() -> Result<(), code::Error> {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let mut state = State::new(&game);
+        let (best_score, _) = state.find_best_move();
+        assert_eq!(state.score_if_solved(), None);
+        for move_to_do in [
+            Move::ChooseNewCode(Code::from_digits(1, 1, 1)?),
+            Move::ChooseVerifier(0.into()),
+            Move::VerifierSolution(VerifierSolution::Cross),
+        ] {
+            state = state.after_move(move_to_do).unwrap().0;
+        }
+        assert_eq!(state.solution(), Some(Code::from_digits(2, 4, 1)?));
+        assert_eq!(state.score_if_solved(), Some(best_score));
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_legal() -> Result<(), code::Error> {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let code = Move::ChooseNewCode(Code::from_digits(1, 1, 1)?);
+        let verifier = Move::ChooseVerifier(0.into());
+        let answer = Move::VerifierSolution(VerifierSolution::Check);
+        let mut state = State::new(&game);
+        // Go through a code, a verifier and an answer, checking every move
+        // against `after_move` in each state.
+        for move_to_do in [code, verifier, answer] {
+            for candidate in [code, verifier, answer] {
+                assert_eq!(
+                    state.is_legal(candidate),
+                    state.after_move(candidate) != Err(AfterMoveError::InvalidMoveError)
+                );
+            }
+            assert!(state.is_legal(move_to_do));
+            state = state.after_move(move_to_do).unwrap().0;
+        }
+        // Answering without a selected verifier is not allowed.
+        assert!(!state.is_legal(answer));
+        assert!(state.is_legal(verifier));
+        Ok(())
+    }
+
+    #[test]
+    fn test_choose_unknown_verifier() -> Result<(), Box<dyn std::error::Error>> {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let (state, _) =
+            State::new(&game).after_move(Move::ChooseNewCode(Code::from_digits(1, 1, 1)?))?;
+        let unknown = "verifier Z".parse::<Move>()?;
+        assert!(!state.is_legal(unknown));
+        assert_eq!(
+            state.after_move(unknown),
+            Err(AfterMoveError::InvalidMoveError)
+        );
+        assert!(state.is_legal("verifier D".parse()?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_answer_split() -> Result<(), code::Error> {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let (state, _) = State::new(&game)
+            .after_move(Move::ChooseNewCode(Code::from_digits(1, 1, 1)?))
+            .unwrap();
+        for verifier in game.iter_verifier_choices() {
+            let (state, _) = state.after_move(Move::ChooseVerifier(verifier)).unwrap();
+            let size_after = |answer| {
+                state
+                    .after_move(Move::VerifierSolution(answer))
+                    .map_or(0, |(state, _)| state.possible_codes().size())
+            };
+            assert_eq!(
+                state.answer_split(verifier),
+                (
+                    size_after(VerifierSolution::Check),
+                    size_after(VerifierSolution::Cross)
+                )
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "a code should be selected")]
+    fn test_answer_split_without_code() {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let _ = State::new(&game).answer_split(0.into());
+    }
+
+    #[test]
+    fn test_sum_scores() {
+        let score = |codes_guessed, verifiers_checked| GameScore {
+            codes_guessed,
+            verifiers_checked,
+        };
+        let scores = [score(2, 5), score(1, 3), score(3, 9)];
+        assert_eq!(scores.into_iter().sum::<GameScore>(), score(6, 17));
+        assert_eq!(std::iter::empty().sum::<GameScore>(), score(0, 0));
+        assert_eq!(
+            [score(200, 1), score(100, 1)]
+                .into_iter()
+                .sum::<GameScore>(),
+            score(u8::MAX, 2)
+        );
+        assert_eq!(score(200, 1).checked_add(score(100, 1)), None);
+    }
+
+    #[test]
+    fn test_move_predicates() -> Result<(), code::Error> {
+        let code = Move::ChooseNewCode(Code::from_digits(1, 2, 3)?);
+        assert!(code.is_code_choice());
+        assert!(!code.is_verifier_choice());
+        assert!(!code.is_answer());
+
+        let verifier = Move::ChooseVerifier(2.into());
+        assert!(!verifier.is_code_choice());
+        assert!(verifier.is_verifier_choice());
+        assert!(!verifier.is_answer());
+
+        for answer in [VerifierSolution::Check, VerifierSolution::Cross] {
+            let answer = Move::VerifierSolution(answer);
+            assert!(!answer.is_code_choice());
+            assert!(!answer.is_verifier_choice());
+            assert!(answer.is_answer());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_strategy_tree() -> Result<(), code::Error> {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let state = State::new(&game);
+        let (_, best_move) = state.find_best_move();
+        assert_eq!(best_move, Move::ChooseNewCode(Code::from_digits(1, 1, 1)?));
+        assert_eq!(
+            state.strategy_tree(),
+            StrategyNode::ChooseCode {
+                code: Code::from_digits(1, 1, 1)?,
+                child: Box::new(StrategyNode::ChooseVerifier {
+                    verifier: 0.into(),
+                    on_check: Some(Box::new(StrategyNode::Solved {
+                        code: Code::from_digits(2, 2, 1)?
+                    })),
+                    on_cross: Some(Box::new(StrategyNode::Solved {
+                        code: Code::from_digits(2, 4, 1)?
+                    })),
+                })
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_possible_moves() -> Result<(), Box<dyn std::error::Error>> {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let state = State::new(&game);
+        // At the start of a round, only a code can be chosen.
+        assert_eq!(state.possible_moves().count(), 125);
+        assert!(state
+            .possible_moves()
+            .all(|move_to_do| move_to_do.is_code_choice()));
+
+        // After choosing a code, a verifier must be chosen.
+        let (state, _) = state.after_move(Move::ChooseNewCode(Code::from_digits(1, 1, 1)?))?;
+        assert_eq!(
+            state.possible_moves().collect::<Vec<_>>(),
+            game.iter_verifier_choices()
+                .map(Move::ChooseVerifier)
+                .collect::<Vec<_>>()
+        );
+
+        // After choosing a verifier, only its answer can follow.
+        let (state, _) = state.after_move(Move::ChooseVerifier(0.into()))?;
+        assert_eq!(
+            state.possible_moves().collect::<Vec<_>>(),
+            vec![
+                Move::VerifierSolution(VerifierSolution::Check),
+                Move::VerifierSolution(VerifierSolution::Cross)
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_current_code() -> Result<(), Box<dyn std::error::Error>> {
+        let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+        let state = State::new(&game);
+        assert_eq!(state.current_code(), None);
+        assert_eq!(state.verifiers_checked_for_current_code(), 0);
+
+        let code = Code::from_digits(1, 2, 3)?;
+        let (state, _) = state.after_move(Move::ChooseNewCode(code))?;
+        assert_eq!(state.current_code(), Some(code));
+        assert_eq!(state.verifiers_checked_for_current_code(), 0);
+
+        assert_eq!(state.awaiting_verifier(), None);
+
+        let (state, _) = state.after_move(Move::ChooseVerifier(1.into()))?;
+        assert_eq!(state.current_code(), Some(code));
+        assert_eq!(state.verifiers_checked_for_current_code(), 1);
+        assert_eq!(
+            state.awaiting_verifier().map(|v| v.to_string()),
+            Some("B".to_string())
+        );
+
+        let (state, _) = state.after_move(Move::VerifierSolution(VerifierSolution::Check))?;
+        assert_eq!(state.awaiting_verifier(), None);
+        let (state, _) = state.after_move(Move::ChooseVerifier(3.into()))?;
+        assert_eq!(state.current_code(), Some(code));
+        assert_eq!(state.verifiers_checked_for_current_code(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_verifiers_checked_per_round() -> Result<(), code::Error> {
+        let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+        let secret = game.possible_solutions().into_iter().next().unwrap();
+        let assignment = game.assignment_for_secret(secret).unwrap();
+        let mut state = State::new(&game);
+        for code in [Code::from_digits(1, 1, 1)?, Code::from_digits(5, 5, 5)?] {
+            state = state.after_move(Move::ChooseNewCode(code)).unwrap().0;
+            for verifier in [0, 1, 2].map(ChosenVerifier::from) {
+                state = state.after_move(Move::ChooseVerifier(verifier)).unwrap().0;
+                assert!(state.verifiers_checked_for_current_code() <= 3);
+                let option = assignment.option_for(verifier).unwrap();
+                let answer = game.verifier_answer(verifier, option, code);
+                state = state.after_move(Move::VerifierSolution(answer)).unwrap().0;
+            }
+            // After three checks of this code, the round is over. In the
+            // second round the total count is six, so comparing the total
+            // against three would have allowed a fourth check here.
+            assert_eq!(state.current_code(), None);
+            assert_eq!(state.verifiers_checked_for_current_code(), 0);
+            assert!(!state.is_legal(Move::ChooseVerifier(3.into())));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_possible_options() -> Result<(), code::Error> {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 48].iter().copied());
+        let state = State::new(&game);
+        // Verifier 48 cannot compare △ and □, since verifier 11 already does.
+        assert_eq!(state.possible_options(3.into()), vec![4, 5, 7, 8]);
+        // For a known code, verifier 48 may still have multiple options.
+        let state = state.intersect_with_set(Set::new_from_code(Code::from_digits(1, 2, 1)?));
+        assert_eq!(state.possible_options(3.into()), vec![4, 8]);
+        // A verifier that is not part of the game has no options.
+        assert_eq!(state.possible_options(4.into()), Vec::<u8>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_answers() -> Result<(), code::Error> {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let mut moves = vec![
+            Move::ChooseNewCode(Code::from_digits(1, 1, 1)?),
+            Move::ChooseVerifier(0.into()),
+            Move::VerifierSolution(VerifierSolution::Cross),
+            Move::ChooseVerifier(1.into()),
+            Move::VerifierSolution(VerifierSolution::Check),
+        ];
+        assert_eq!(State::validate_answers(&game, &moves), Ok(()));
+        moves[4] = Move::VerifierSolution(VerifierSolution::Cross);
+        assert_eq!(
+            State::validate_answers(&game, &moves),
+            Err((4, AfterMoveError::NoCodesLeft))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_best_moves() {
+        // Any first code allows finding the solution using a single verifier.
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let state = State::new(&game);
+        let (best_score, best_move) = state.find_best_move();
+        let (score, best_moves) = state.find_best_moves();
+        assert_eq!(score, best_score);
+        assert_eq!(best_moves.len(), 125);
+        assert!(best_moves.contains(&best_move));
+    }
+
+    #[test]
+    fn test_search_stats() {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let state = State::new(&game);
+        let (_, _, stats) = state.find_best_move_instrumented();
+        assert!(stats.nodes_visited > 0);
+        assert_eq!(stats, state.find_best_move_instrumented().2);
+    }
+
+    #[test]
+    fn test_declare_solution() {
+        // There are only two possible codes, but declaring one of them may
+        // lose the game, so checking a verifier is better.
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let state = State::new(&game);
+        assert_eq!(state.possible_codes().size(), 2);
+        let (score, action) = state.find_best_action(false);
+        assert_eq!(
+            score,
+            Some(GameScore {
+                codes_guessed: 1,
+                verifiers_checked: 1
+            })
+        );
+        assert_eq!(state.find_best_action(true), (score, action));
+    }
+
+    #[test]
+    fn test_declare_indistinguishable_solutions() -> Result<(), Box<dyn std::error::Error>> {
+        // No verifier can tell the possible codes apart, so guessing is the
+        // only way to end the game, even though it may be lost.
+        let game: Game = "20 36".parse()?;
+        let state = State::new(&game);
+        assert!(state.possible_codes().size() > 1);
+        assert_eq!(
+            solve_many(std::slice::from_ref(&game)),
+            vec![Err(FindMoveError::Indistinguishable)]
+        );
+        let (score, action) = state.find_best_action(true);
+        assert_eq!(score, None);
+        assert!(
+            matches!(action, Action::DeclareSolution(code) if state.possible_codes().contains(code))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_declare_solution_early() -> Result<(), Box<dyn std::error::Error>> {
+        // A verifier can tell whether the code contains a 3 or a 4, but none
+        // can tell where that digit is. The game may be lost whatever the
+        // player does, so declaring a code right away beats checking a
+        // verifier first.
+        let game: Game = "23 40".parse()?;
+        let state = State::new(&game);
+        assert_eq!(state.possible_codes().size(), 6);
+        let (score, action) = state.find_best_action(true);
+        assert_eq!(score, None);
+        assert!(
+            matches!(action, Action::DeclareSolution(code) if state.possible_codes().contains(code))
+        );
+        let declaration_score = StateScore::loss_for(Objective::FewestRounds, 0, 0);
+        let mut search = Search {
+            allow_guessing: true,
+            ..Search::default()
+        };
+        for move_to_do in state.possible_moves() {
+            let score = state.score_after_move(move_to_do, |state| {
+                state
+                    .alphabeta(
+                        StateScore::min_score(),
+                        StateScore::max_score(),
+                        &mut search,
+                    )
+                    .0
+            });
+            assert!(score < declaration_score);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+        let dot = State::new(&game).to_dot();
+        assert!(dot.starts_with("digraph"));
+        assert!(dot.contains("->"));
+        assert!(dot.contains("solution 241"));
+    }
+
+    #[test]
+    fn test_verifier_solution_from_char() {
+        for (c, solution) in [
+            ('x', VerifierSolution::Cross),
+            ('X', VerifierSolution::Cross),
+            ('v', VerifierSolution::Check),
+            ('V', VerifierSolution::Check),
+        ] {
+            assert_eq!(VerifierSolution::try_from(c), Ok(solution));
+        }
+        for c in ['y', '✓', ' '] {
+            assert_eq!(
+                VerifierSolution::try_from(c),
+                Err(VerifierSolutionParseError(c))
+            );
+        }
+    }
+
+    #[test]
+    fn test_verifier_solution_display() {
+        assert_eq!(VerifierSolution::Check.to_string(), "✓");
+        assert_eq!(VerifierSolution::Cross.to_string(), "✗");
+    }
+
+    #[test]
+    fn test_expected_split_for_code() -> Result<(), Box<dyn std::error::Error>> {
+        let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+        let (state, _) = State::new(&game).after_move("code 221".parse()?)?;
+        let (state, _) = state.after_move("verifier A".parse()?)?;
+        let (state, _) = state.after_move("answer cross".parse()?)?;
+        // The verifiers split the remaining codes less evenly for 444.
+        let uninformative = state.expected_split_for_code(Code::from_digits(4, 4, 4)?);
+        let informative = state.expected_split_for_code(Code::from_digits(2, 3, 4)?);
+        assert!(informative > uninformative);
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_notation_round_trip() {
+        let moves = [
+            Move::ChooseNewCode(Code::from_digits(3, 5, 1).unwrap()),
+            Move::ChooseVerifier(3.into()),
+            Move::ChooseVerifier(27.into()),
+            Move::VerifierSolution(VerifierSolution::Check),
+        ];
+        for (move_to_do, notation) in
+            moves
+                .into_iter()
+                .zip(["code 351", "verifier D", "verifier B1", "answer check"])
+        {
+            assert_eq!(move_to_do.to_string(), notation);
+            assert_eq!(notation.parse(), Ok(move_to_do));
+        }
+    }
+
+    #[test]
+    fn test_move_notation_oversized_round() {
+        assert_eq!(
+            "verifier A1000000000000000000".parse::<Move>(),
+            Err(MoveParseError::InvalidVerifier)
+        );
+        assert_eq!(
+            format!("verifier Z{}", usize::MAX / 26).parse::<Move>(),
+            Err(MoveParseError::InvalidVerifier)
+        );
+        assert_eq!(
+            format!("verifier A{}", usize::MAX).parse::<Move>(),
+            Err(MoveParseError::InvalidVerifier)
+        );
+    }
+
+    #[test]
+    fn test_move_notation_invalid() {
+        assert_eq!("code".parse::<Move>(), Err(MoveParseError::InvalidFormat));
+        assert_eq!(
+            "code 351 answer".parse::<Move>(),
+            Err(MoveParseError::InvalidFormat)
+        );
+        assert_eq!(
+            "code 361".parse::<Move>(),
+            Err(MoveParseError::InvalidCode(code::Error::InvalidDigits))
+        );
+        assert_eq!(
+            "code 3511".parse::<Move>(),
+            Err(MoveParseError::InvalidCode(code::Error::InvalidDigits))
+        );
+        assert_eq!(
+            "verifier AB".parse::<Move>(),
+            Err(MoveParseError::InvalidVerifier)
+        );
+        assert_eq!(
+            "answer yes".parse::<Move>(),
+            Err(MoveParseError::InvalidAnswer)
+        );
+    }
+
+    use proptest::prelude::*;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+        // The pruned search should always find the same score as the full
+        // search.
+        #[test]
+        fn test_alphabeta_matches_minimax(
+            verifier_numbers in proptest::collection::btree_set(1..=48usize, 2)
+        ) {
+            let game = Game::new_from_verifier_numbers(verifier_numbers.into_iter());
+            let state = State::new(&game);
+            // The full search branches on every code after every check, so it
+            // is only feasible for a few possible codes.
+            prop_assume!((2..=3).contains(&state.possible_codes().size()));
+            let (alphabeta_score, _) = state.alphabeta(
+                StateScore::min_score(),
+                StateScore::max_score(),
+                &mut Search::default(),
+            );
+            let (minimax_score, _) = state.minimax_reference();
+            prop_assert_eq!(alphabeta_score, minimax_score);
+        }
+
+        // Playing the best moves against any secret of a well-posed game
+        // should find exactly that secret, within the predicted score.
+        #[test]
+        fn test_solver_finds_secret(
+            verifier_numbers in proptest::collection::btree_set(1..=48usize, 2),
+            secret_index in any::<prop::sample::Index>()
+        ) {
+            let game = Game::new_from_verifier_numbers(verifier_numbers.into_iter());
+            let solutions: Vec<_> = game
+                .all_assignments()
+                .filter(|assignment| game.is_possible_solution(assignment))
+                .collect();
+            prop_assume!(!solutions.is_empty());
+            let assignment = &solutions[secret_index.index(solutions.len())];
+            let secret = game
+                .possible_codes_for_assignment(assignment)
+                .into_iter()
+                .next()
+                .unwrap();
+
+            let mut state = State::new(&game);
+            // Some games have several solutions that no verifier can tell
+            // apart, so there is no strategy at all.
+            let (expected_score, _) = state.alphabeta(
+                StateScore::min_score(),
+                StateScore::max_score(),
+                &mut Search::default(),
+            );
+            let expected_score = expected_score.codes_and_verifiers_checked();
+            prop_assume!(expected_score.is_some());
+            let mut score = GameScore {
+                codes_guessed: 0,
+                verifiers_checked: 0,
+            };
+            while !state.is_solved() {
+                let move_to_do =
+                    if let CodeVerifierChoice::CodeAndVerifier(code, verifier) = state.current_selection {
+                        let index = game
+                            .iter_verifier_choices()
+                            .position(|chosen_verifier| chosen_verifier == verifier)
+                            .unwrap();
+                        let option = game
+                            .verifier_options_for_assignment(assignment)
+                            .nth(index)
+                            .unwrap();
+                        Move::VerifierSolution(if option.code_set().contains(code) {
+                            VerifierSolution::Check
+                        } else {
+                            VerifierSolution::Cross
+                        })
+                    } else {
+                        state.find_best_move().1
+                    };
+                match move_to_do {
+                    Move::ChooseNewCode(_) => score.codes_guessed += 1,
+                    Move::ChooseVerifier(_) => score.verifiers_checked += 1,
+                    Move::VerifierSolution(_) => {}
+                }
+                state = state.after_move(move_to_do).unwrap().0;
+            }
+            prop_assert_eq!(state.solution(), Some(secret));
+            let expected_score = expected_score.unwrap();
+            prop_assert!(score.codes_guessed <= expected_score.codes_guessed);
+            prop_assert!(score.verifiers_checked <= expected_score.verifiers_checked);
+        }
+    }
+
+    proptest! {
+        // Parsing arbitrary input should never panic, and any move that is
+        // parsed should survive a round trip through its notation.
+        #[test]
+        fn test_parse_arbitrary_move(input in any::<String>()) {
+            if let Ok(move_to_do) = input.parse::<Move>() {
+                prop_assert_eq!(move_to_do.to_string().parse::<Move>(), Ok(move_to_do));
+            }
+        }
+
+        // The same for verifier moves, which arbitrary strings rarely are. Long
+        // rounds should be rejected instead of overflowing.
+        #[test]
+        fn test_parse_arbitrary_verifier(
+            value in prop_oneof!["\\PC*", "[a-zA-Z][0-9]{0,25}"]
+        ) {
+            if let Ok(move_to_do) = format!("verifier {value}").parse::<Move>() {
+                prop_assert!(matches!(move_to_do, Move::ChooseVerifier(_)));
+                prop_assert_eq!(move_to_do.to_string().parse::<Move>(), Ok(move_to_do));
+            }
+        }
+    }
 }
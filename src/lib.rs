@@ -1,8 +1,19 @@
 #![doc = include_str!("../Readme.md")]
 #![deny(rustdoc::broken_intra_doc_links)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 #[deny(missing_copy_implementations)]
 #[deny(missing_docs)]
 pub mod code;
+mod error;
+#[cfg(feature = "std")]
 pub mod game;
+#[cfg(feature = "std")]
 pub mod gametree;
+#[cfg(feature = "std")]
+pub mod session;
 pub mod verifier;
+
+pub use error::Error;
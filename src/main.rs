@@ -1,32 +1,164 @@
 use std::io::stdin;
+use std::{env, process};
 
-use turing_machine_ai::game::Game;
+use turing_machine_ai::code::Code;
+use turing_machine_ai::game::{Assignment, Game};
 use turing_machine_ai::gametree::{
-    self, AfterMoveError, AfterMoveInfo, Move, State, VerifierSolution,
+    self, AfterMoveError, AfterMoveInfo, Move, Objective, State, StrategyNode, VerifierSolution,
 };
 
+const USAGE: &str = "Usage: turing-machine-ai [--strategy] [--fewest-rounds | --fewest-total] [--secret <CODE>] <VERIFIER NUMBER>...
+
+Solve a game interactively. The verifiers are given by their card numbers,
+between 1 and 48, for example:
+
+    turing-machine-ai 12 16 18 19 21
+
+Options:
+    --strategy       Print the complete optimal strategy instead of playing
+    --fewest-rounds  Minimize the number of rounds first, and then the
+                     number of verifier checks (default)
+    --fewest-total   Minimize the total number of codes and verifier checks
+    --secret <CODE>  Answer the verifiers for the given secret code, such as
+                     241, instead of asking for the answers";
+
 fn main() {
-    let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+    let mut print_strategy_only = false;
+    let mut secret = None;
+    let mut objective = Objective::FewestRounds;
+    let mut verifier_numbers = Vec::new();
+    let mut arguments = env::args().skip(1);
+    while let Some(argument) = arguments.next() {
+        if argument == "--strategy" {
+            print_strategy_only = true;
+        } else if argument == "--fewest-rounds" {
+            objective = Objective::FewestRounds;
+        } else if argument == "--fewest-total" {
+            objective = Objective::FewestTotal;
+        } else if argument == "--secret" {
+            let Some(code) = arguments.next().as_deref().and_then(parse_code) else {
+                eprintln!("--secret expects a code such as 241\n\n{USAGE}");
+                process::exit(2);
+            };
+            secret = Some(code);
+        } else {
+            verifier_numbers.push(argument);
+        }
+    }
+    let game: Game = match verifier_numbers.join(" ").parse() {
+        Ok(game) => game,
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    let state = State::new(&game);
+    if state.possible_codes().size() == 0 {
+        eprintln!("This game does not have a solution.");
+        process::exit(1);
+    }
+    if print_strategy_only {
+        print_strategy(&state.strategy_tree_with_objective(objective), 0);
+        if !state.is_solved() {
+            let (score, _) = state.find_best_move_with_objective(objective);
+            println!("In the worst case, the solution is found using {score}.");
+        }
+    } else {
+        let secret_assignment = secret.map(|secret| {
+            game.assignment_for_secret(secret).unwrap_or_else(|| {
+                let (triangle, square, circle) = secret.digits();
+                eprintln!("{triangle}{square}{circle} is not a solution of this game.");
+                process::exit(2);
+            })
+        });
+        play(&game, state, objective, secret_assignment.as_ref());
+    }
+}
 
-    let mut state = State::new(&game);
+/// Parse a code written as three digits, such as 241.
+fn parse_code(code: &str) -> Option<Code> {
+    let digits: Vec<u8> = code
+        .chars()
+        .map(|c| c.to_digit(10).and_then(|digit| u8::try_from(digit).ok()))
+        .collect::<Option<_>>()?;
+    Code::try_from(<[u8; 3]>::try_from(digits).ok()?).ok()
+}
+
+/// Print the strategy, starting with the given node, branching on every
+/// verifier answer.
+fn print_strategy(node: &StrategyNode, depth: usize) {
+    let indentation = "  ".repeat(depth);
+    match node {
+        StrategyNode::ChooseCode { code, child } => {
+            println!("{indentation}{}", Move::ChooseNewCode(*code));
+            print_strategy(child, depth);
+        }
+        StrategyNode::ChooseVerifier {
+            verifier,
+            on_check,
+            on_cross,
+        } => {
+            println!("{indentation}{}", Move::ChooseVerifier(*verifier));
+            for (answer, child) in [
+                (VerifierSolution::Check, on_check),
+                (VerifierSolution::Cross, on_cross),
+            ] {
+                if let Some(child) = child {
+                    println!("{indentation}{}", Move::VerifierSolution(answer));
+                    print_strategy(child, depth + 1);
+                }
+            }
+        }
+        StrategyNode::Solved { code } => {
+            let (triangle, square, circle) = code.digits();
+            println!("{indentation}solution {triangle}{square}{circle}");
+        }
+    }
+}
+
+/// Play the game interactively, asking for the verifier answers. If the
+/// assignment of the secret is known, the answers are derived from it instead.
+fn play(
+    game: &Game,
+    mut state: State,
+    objective: Objective,
+    secret_assignment: Option<&Assignment>,
+) {
     while !state.is_solved() {
         println!(
             "There are still {} possible codes.",
             state.possible_codes().size()
         );
-        if state.is_awaiting_result() {
+        if let (Some(assignment), Some(verifier), Some(code)) = (
+            secret_assignment,
+            state.awaiting_verifier(),
+            state.current_code(),
+        ) {
+            let secret_option = assignment
+                .option_for(verifier)
+                .expect("the chosen verifier should be part of the game");
+            let answer = game.verifier_answer(verifier, secret_option, code);
+            println!("The verifier answers {answer}.");
+            state = state
+                .after_move(Move::VerifierSolution(answer))
+                .expect("the answer for the secret should be consistent")
+                .0;
+        } else if state.is_awaiting_result() {
             loop {
                 println!("What does the verifier tell you? x/v");
                 let mut string = String::new();
                 stdin().read_line(&mut string).unwrap();
-                let state_result = match string.trim() {
-                    "x" | "X" => state.after_move(Move::VerifierSolution(VerifierSolution::Cross)),
-                    "v" | "V" => state.after_move(Move::VerifierSolution(VerifierSolution::Check)),
-                    other => {
-                        println!("Unknown selection: '{other}'");
-                        continue;
-                    }
+                let mut chars = string.trim().chars();
+                let verifier_solution = match (chars.next(), chars.next()) {
+                    (Some(c), None) => VerifierSolution::try_from(c).ok(),
+                    _ => None,
+                };
+                let Some(verifier_solution) = verifier_solution else {
+                    println!("Unknown selection: '{}'", string.trim());
+                    continue;
                 };
+                let state_result = state.after_move(Move::VerifierSolution(verifier_solution));
                 match state_result {
                     Err(AfterMoveError::InvalidMoveError) => panic!("Invalid move!"),
                     Err(AfterMoveError::NoCodesLeft) => panic!("No codes left!"),
@@ -40,9 +172,9 @@ fn main() {
                 }
             }
         } else {
-            let (score, move_to_do) = state.find_best_move();
+            let (score, move_to_do) = state.find_best_move_with_objective(objective);
             println!(
-                "You will find the solution in {} codes and {} verifier checks.",
+                "You will find the solution in {} rounds and {} verifier checks.",
                 score.codes_guessed, score.verifiers_checked
             );
             match state.after_move(move_to_do) {
@@ -69,8 +201,6 @@ fn main() {
         }
     }
 
-    println!(
-        "Solved! Solution: {:?}",
-        state.possible_codes().into_iter().next().unwrap()
-    );
+    let (triangle, square, circle) = state.solution().unwrap().digits();
+    println!("Solved! Solution: {triangle}{square}{circle}");
 }
@@ -0,0 +1,160 @@
+//! This module contains [`GameSession`], which keeps track of the moves made
+//! in a game so that they can be undone.
+
+use crate::{
+    code::Set,
+    game::Game,
+    gametree::{AfterMoveError, AfterMoveInfo, GameScore, Move, State},
+};
+
+/// A game that is being played. The session owns the game and records every
+/// move that was applied, so that moves can be undone. This avoids having to
+/// keep the game alive separately from its [`State`].
+///
+/// # Example
+/// ```
+/// use turing_machine_ai::{code::Code, game::Game, gametree::Move, session::GameSession};
+///
+/// let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+/// let mut session = GameSession::new(game);
+/// let code = Move::ChooseNewCode(Code::from_digits(1, 1, 1)?);
+/// session.after_move(code).unwrap();
+/// assert!(session.state().has_selected_code());
+/// assert_eq!(session.undo(), Some(code));
+/// assert!(!session.state().has_selected_code());
+/// # Ok::<(), turing_machine_ai::code::Error>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GameSession {
+    game: Game,
+    /// The possible solutions of the game, which are expensive to compute.
+    possible_solutions: Set,
+    moves: Vec<Move>,
+}
+
+impl GameSession {
+    /// Start a new session for the given game.
+    #[must_use]
+    pub fn new(game: Game) -> Self {
+        let possible_solutions = game.possible_solutions();
+        GameSession {
+            game,
+            possible_solutions,
+            moves: Vec::new(),
+        }
+    }
+
+    /// Get the game that is being played.
+    #[must_use]
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Get the moves that were applied, in order.
+    #[must_use]
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Get the current state of the game, after all moves were applied. The
+    /// moves are replayed from the start of the game.
+    #[must_use]
+    pub fn state(&self) -> State<'_> {
+        self.moves.iter().fold(
+            State::with_possible_codes(&self.game, self.possible_solutions),
+            |state, move_to_do| {
+                state
+                    .after_move(*move_to_do)
+                    .expect("recorded moves should be valid")
+                    .0
+            },
+        )
+    }
+
+    /// Apply the move to the current state and record it.
+    ///
+    /// # Errors
+    /// If the move cannot be applied, the error of [`State::after_move`] is
+    /// returned and the move is not recorded.
+    pub fn after_move(
+        &mut self,
+        move_to_do: Move,
+    ) -> Result<Option<AfterMoveInfo>, AfterMoveError> {
+        let (_, info) = self.state().after_move(move_to_do)?;
+        self.moves.push(move_to_do);
+        Ok(info)
+    }
+
+    /// Returns whether the game has been solved. See [`State::is_solved`].
+    #[must_use]
+    pub fn is_solved(&self) -> bool {
+        self.state().is_solved()
+    }
+
+    /// Find the best move for the current state. See
+    /// [`State::find_best_move`].
+    ///
+    /// # Panics
+    /// This function will panic if the state is currently awaiting a verifier
+    /// answer or if the game has already been solved.
+    #[must_use]
+    pub fn find_best_move(&self) -> (GameScore, Move) {
+        self.state().find_best_move()
+    }
+
+    /// Undo the last move, returning it. If no moves were made, `None` is
+    /// returned.
+    pub fn undo(&mut self) -> Option<Move> {
+        self.moves.pop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        code::{self, Code},
+        game::Game,
+        gametree::{AfterMoveError, Move, State, VerifierSolution},
+    };
+
+    use super::GameSession;
+
+    #[test]
+    fn test_undo() -> Result<(), Box<dyn std::error::Error>> {
+        let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+        let mut session = GameSession::new(game.clone());
+        let moves = [
+            Move::ChooseNewCode(Code::from_digits(1, 2, 3)?),
+            Move::ChooseVerifier(1.into()),
+            Move::VerifierSolution(VerifierSolution::Check),
+        ];
+        for move_to_do in moves {
+            session.after_move(move_to_do)?;
+        }
+        assert_eq!(session.moves(), moves);
+
+        assert_eq!(session.undo(), Some(moves[2]));
+        assert_eq!(session.undo(), Some(moves[1]));
+        assert_eq!(session.moves(), &moves[..1]);
+        let (expected, _) = State::new(&game).after_move(moves[0])?;
+        assert_eq!(session.state(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_move_is_not_recorded() -> Result<(), code::Error> {
+        let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+        let mut session = GameSession::new(game);
+        assert_eq!(
+            session.after_move(Move::ChooseVerifier(0.into())),
+            Err(AfterMoveError::InvalidMoveError)
+        );
+        assert!(session.moves().is_empty());
+        assert_eq!(session.undo(), None);
+        session
+            .after_move(Move::ChooseNewCode(Code::from_digits(1, 1, 1)?))
+            .unwrap();
+        assert_eq!(session.moves().len(), 1);
+        Ok(())
+    }
+}
@@ -1,15 +1,24 @@
 //! This module contains verifier specific code, including descriptions of all default verifiers.
 
-use std::fmt::Debug;
+use core::fmt::Debug;
+use core::hash::{Hash, Hasher};
 
 use arrayvec::ArrayVec;
 
-use crate::code::{Code, SequenceOrder, Set};
+use crate::code::{Code, Color, SequenceOrder, Set};
+
+/// The number of verifier cards that can be obtained through
+/// [`get_verifier_by_number`].
+pub const NUMBER_OF_VERIFIERS: usize = 48;
 
 /// Get a verifier by its (one-indexed) number in the game.
+///
+/// # Panics
+/// This function panics if the number does not lie between 1 and
+/// [`NUMBER_OF_VERIFIERS`].
 #[must_use]
 pub fn get_verifier_by_number(number: usize) -> Verifier {
-    let verifiers: [Verifier; 48] = [
+    let verifiers: [Verifier; NUMBER_OF_VERIFIERS] = [
         // 1
         Verifier::from_description_and_options(
             "the △ number compared to 1",
@@ -758,14 +767,16 @@ pub fn get_verifier_by_number(number: usize) -> Verifier {
             ],
         ),
     ];
-    verifiers[number - 1].clone()
+    let mut verifier = verifiers[number - 1].clone();
+    verifier.number = Some(number);
+    verifier
 }
 
 const MAX_VERIFIER_OPTIONS: usize = 9;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
 pub struct VerifierOption {
-    pub(crate) description: &'static str,
+    description: &'static str,
     code_set: Set,
 }
 
@@ -775,6 +786,20 @@ impl VerifierOption {
         self.code_set
     }
 
+    /// Get the description of this option, as printed on the card.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::verifier::get_verifier_by_number;
+    ///
+    /// let verifier = get_verifier_by_number(11);
+    /// assert_eq!(verifier.option(0).description(), "△ < □");
+    /// ```
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        self.description
+    }
+
     pub fn from_description_and_closure(
         description: &'static str,
         checker: fn(Code) -> bool,
@@ -786,11 +811,13 @@ impl VerifierOption {
     }
 }
 
+#[cfg(feature = "std")]
 pub(crate) trait Intersection {
     type To;
     fn intersect(self) -> Self::To;
 }
 
+#[cfg(feature = "std")]
 impl<T: Iterator<Item = Set>> Intersection for T {
     type To = Set;
     fn intersect(self) -> Self::To {
@@ -800,17 +827,39 @@ impl<T: Iterator<Item = Set>> Intersection for T {
     }
 }
 
-#[derive(Clone, Eq, PartialEq, Hash)]
+/// A verifier, consisting of a description and the options it may have.
+///
+/// Equality is structural: two verifiers are equal if their descriptions and
+/// options are equal. The card number is ignored, so a custom verifier equals
+/// the card it copies. To check whether two verifiers are the same card, use
+/// [`Verifier::same_card`].
+#[derive(Clone)]
 pub struct Verifier {
+    number: Option<usize>,
     description: &'static str,
     options: ArrayVec<VerifierOption, MAX_VERIFIER_OPTIONS>,
 }
 
+impl PartialEq for Verifier {
+    fn eq(&self, other: &Self) -> bool {
+        self.description == other.description && self.options == other.options
+    }
+}
+
+impl Eq for Verifier {}
+
+impl Hash for Verifier {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.description.hash(state);
+        self.options.hash(state);
+    }
+}
+
 impl Debug for Verifier {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         writeln!(f, "{}", self.description)?;
         for option in &self.options {
-            writeln!(f, "- {}", option.description)?;
+            writeln!(f, "- {}", option.description())?;
         }
         Ok(())
     }
@@ -822,6 +871,20 @@ impl Verifier {
         self.description
     }
 
+    /// The (one-indexed) card number of this verifier, if it was obtained
+    /// through [`get_verifier_by_number`].
+    #[must_use]
+    pub const fn number(&self) -> Option<usize> {
+        self.number
+    }
+
+    /// Returns whether both verifiers are the same card, by comparing their
+    /// card numbers. Verifiers without a card number are never the same card.
+    #[must_use]
+    pub fn same_card(&self, other: &Verifier) -> bool {
+        self.number.is_some() && self.number == other.number
+    }
+
     #[must_use]
     pub const fn number_of_options(&self) -> usize {
         self.options.len()
@@ -833,17 +896,217 @@ impl Verifier {
         options: &[VerifierOption],
     ) -> Self {
         Self {
+            number: None,
             description,
             options: options.iter().copied().collect(),
         }
     }
 
+    /// Get the option with the given index.
+    ///
+    /// # Panics
+    /// This function panics if the verifier does not have an option with
+    /// this index. See [`Verifier::try_option`] for a non-panicking variant.
     #[must_use]
     pub fn option(&self, choice: u8) -> &VerifierOption {
-        &self.options[choice as usize]
+        self.try_option(choice)
+            .expect("the verifier should have an option with this index")
+    }
+
+    /// Get the option with the given index, or `None` if the verifier does
+    /// not have an option with this index.
+    #[must_use]
+    pub fn try_option(&self, choice: u8) -> Option<&VerifierOption> {
+        self.options.get(usize::from(choice))
     }
 
     pub fn options(&self) -> impl Iterator<Item = &VerifierOption> + '_ {
         self.options.iter()
     }
+
+    /// Get the union of the code sets of all options, i.e. all codes that
+    /// satisfy at least one of the options. For most verifier cards of the
+    /// game, this is [`Set::all`].
+    #[must_use]
+    pub fn union_of_options(&self) -> Set {
+        self.options()
+            .map(VerifierOption::code_set)
+            .fold(Set::empty(), Set::union_with)
+    }
+
+    /// Get the colours whose number can influence the answer of this verifier.
+    /// A colour is involved if changing only its number can change whether a
+    /// code satisfies one of the options. The colours are returned in the
+    /// order triangle, square, circle.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::{code::Color, verifier::get_verifier_by_number};
+    ///
+    /// // The □ number compared to 4.
+    /// assert_eq!(get_verifier_by_number(4).involved_colors().as_slice(), [Color::Square]);
+    /// // The number of 1s in the code.
+    /// assert_eq!(get_verifier_by_number(8).involved_colors().len(), 3);
+    /// ```
+    #[must_use]
+    pub fn involved_colors(&self) -> ArrayVec<Color, 3> {
+        [Color::Triangle, Color::Square, Color::Circle]
+            .into_iter()
+            .filter(|&color| {
+                self.options().any(|option| {
+                    let code_set = option.code_set();
+                    Set::all().into_iter().any(|code| {
+                        (1..=5).any(|number| {
+                            let mut digits = code.digits_array();
+                            digits[color as usize] = number;
+                            Code::try_from(digits).is_ok_and(|changed| {
+                                code_set.contains(code) != code_set.contains(changed)
+                            })
+                        })
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// Returns whether every code satisfies exactly one of the options of
+    /// this verifier. In other words, the options cover all codes and do not
+    /// overlap.
+    ///
+    /// # Example
+    /// ```
+    /// use turing_machine_ai::verifier::get_verifier_by_number;
+    ///
+    /// assert!(get_verifier_by_number(5).partitions_all_codes());
+    /// // Verifier 48 compares multiple pairs of colours at the same time.
+    /// assert!(!get_verifier_by_number(48).partitions_all_codes());
+    /// ```
+    #[must_use]
+    pub fn partitions_all_codes(&self) -> bool {
+        let total_size: u32 = self.options().map(|option| option.code_set().size()).sum();
+        let union = self.union_of_options();
+        // The options are disjoint exactly when no code is counted twice.
+        union == Set::all() && total_size == union.size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{DefaultHasher, Hash, Hasher};
+
+    use super::{get_verifier_by_number, Verifier, VerifierOption};
+    use crate::code::{Color, Set};
+
+    #[test]
+    fn test_union_of_options() {
+        assert_eq!(get_verifier_by_number(11).union_of_options(), Set::all());
+        // Verifier 14 does not classify codes without a unique smallest digit.
+        assert_eq!(
+            get_verifier_by_number(14).union_of_options(),
+            Set::from_closure(|code| {
+                let [smallest, second, _] = code.sorted_digits();
+                smallest < second
+            })
+        );
+
+        let incomplete = Verifier::from_description_and_options(
+            "the △ number compared to 3",
+            &[
+                VerifierOption::from_description_and_closure("△ < 3", |code| code.triangle() < 3),
+                VerifierOption::from_description_and_closure("△ > 3", |code| code.triangle() > 3),
+            ],
+        );
+        assert_eq!(
+            incomplete.union_of_options(),
+            Set::from_closure(|code| code.triangle() != 3)
+        );
+    }
+
+    #[test]
+    fn test_try_option() {
+        let verifier = get_verifier_by_number(4);
+        assert_eq!(
+            verifier.try_option(0).map(VerifierOption::description),
+            Some("□ < 4")
+        );
+        assert_eq!(
+            verifier.try_option(2).map(VerifierOption::description),
+            Some("□ > 4")
+        );
+        assert_eq!(verifier.try_option(2), Some(verifier.option(2)));
+        assert_eq!(verifier.try_option(3), None);
+        assert_eq!(verifier.try_option(u8::MAX), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "the verifier should have an option with this index")]
+    fn test_option_out_of_range() {
+        let _ = get_verifier_by_number(4).option(3);
+    }
+
+    #[test]
+    fn test_involved_colors() {
+        assert_eq!(
+            get_verifier_by_number(1).involved_colors().as_slice(),
+            [Color::Triangle]
+        );
+        assert_eq!(
+            get_verifier_by_number(12).involved_colors().as_slice(),
+            [Color::Triangle, Color::Circle]
+        );
+        assert_eq!(
+            get_verifier_by_number(18).involved_colors().as_slice(),
+            [Color::Triangle, Color::Square, Color::Circle]
+        );
+    }
+
+    #[test]
+    fn test_same_card() {
+        let card = get_verifier_by_number(11);
+        assert!(card.same_card(&get_verifier_by_number(11)));
+        assert!(!card.same_card(&get_verifier_by_number(12)));
+
+        let custom = Verifier::from_description_and_options(
+            card.description(),
+            &card.options().copied().collect::<Vec<_>>(),
+        );
+        assert!(!card.same_card(&custom));
+        assert!(!custom.same_card(&custom));
+        assert_eq!(card, custom);
+        assert_eq!(custom.clone(), custom);
+
+        let hash = |verifier: &Verifier| {
+            let mut hasher = DefaultHasher::new();
+            verifier.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&card), hash(&custom));
+    }
+
+    #[test]
+    fn test_partitions_all_codes() {
+        assert!(get_verifier_by_number(11).partitions_all_codes());
+
+        let overlapping = Verifier::from_description_and_options(
+            "the △ number compared to 3",
+            &[
+                VerifierOption::from_description_and_closure("△ <= 3", |code| {
+                    code.triangle() <= 3
+                }),
+                VerifierOption::from_description_and_closure("△ >= 3", |code| {
+                    code.triangle() >= 3
+                }),
+            ],
+        );
+        assert!(!overlapping.partitions_all_codes());
+
+        let incomplete = Verifier::from_description_and_options(
+            "the △ number compared to 3",
+            &[
+                VerifierOption::from_description_and_closure("△ < 3", |code| code.triangle() < 3),
+                VerifierOption::from_description_and_closure("△ > 3", |code| code.triangle() > 3),
+            ],
+        );
+        assert!(!incomplete.partitions_all_codes());
+    }
 }
@@ -5,6 +5,7 @@ use turing_machine_ai::{
     code::Code,
     game::Game,
     gametree::{Move, State},
+    session::GameSession,
 };
 
 #[test]
@@ -39,21 +40,28 @@ fn test_01() -> Result<(), Box<dyn Error>> {
 fn test_02() -> Result<(), Box<dyn Error>> {
     let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
     let state = State::new(&game);
-    let (_, next_move) = state.find_best_move();
-    assert_eq!(next_move, Move::ChooseNewCode(Code::from_digits(2, 2, 1)?));
+    let (game_score, next_move) = state.find_best_move();
+    assert_eq!(game_score.codes_guessed, 2);
+    assert_eq!(game_score.verifiers_checked, 3);
+    assert_eq!(next_move, Move::ChooseNewCode(Code::from_digits(1, 1, 1)?));
 
     let (state, _) = state.after_move(next_move)?;
     let (_, next_move) = state.find_best_move();
-    assert_eq!(next_move, Move::ChooseVerifier(0.into()));
+    assert_eq!(next_move, Move::ChooseVerifier(2.into()));
 
     let (state, _) = state.after_move(next_move)?;
     let (state, _) = state.after_move(Move::VerifierSolution(Cross))?;
     let (_, next_move) = state.find_best_move();
     assert_eq!(next_move, Move::ChooseVerifier(1.into()));
 
+    // After a single check of the code, a new code may be chosen.
     let (state, _) = state.after_move(next_move)?;
     let (state, _) = state.after_move(Move::VerifierSolution(Check))?;
     let (_, next_move) = state.find_best_move();
+    assert_eq!(next_move, Move::ChooseNewCode(Code::from_digits(2, 2, 1)?));
+
+    let (state, _) = state.after_move(next_move)?;
+    let (_, next_move) = state.find_best_move();
     assert_eq!(next_move, Move::ChooseVerifier(3.into()));
 
     let (state, _) = state.after_move(next_move)?;
@@ -66,3 +74,29 @@ fn test_02() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_01_session() -> Result<(), Box<dyn Error>> {
+    let mut session = GameSession::new(Game::new_from_verifier_numbers(
+        [4, 9, 11, 14].iter().copied(),
+    ));
+    let (game_score, next_move) = session.find_best_move();
+    assert_eq!(game_score.codes_guessed, 1);
+    assert_eq!(game_score.verifiers_checked, 1);
+    assert_eq!(next_move, Move::ChooseNewCode(Code::from_digits(1, 1, 1)?));
+    session.after_move(next_move)?;
+
+    let (_, next_move) = session.find_best_move();
+    assert_eq!(next_move, Move::ChooseVerifier(0.into()));
+    session.after_move(next_move)?;
+    assert!(!session.is_solved());
+
+    session.after_move(Move::VerifierSolution(Cross))?;
+    assert!(session.is_solved());
+    assert_eq!(
+        session.state().solution(),
+        Some(Code::from_digits(2, 4, 1)?)
+    );
+
+    Ok(())
+}
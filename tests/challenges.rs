@@ -1,7 +1,7 @@
 use std::error::Error;
 
 use turing_machine_ai::{
-    code::Code,
+    code::{Code, Set},
     game::Game,
     gametree::{Move, VerifierSolution::*},
 };
@@ -28,3 +28,17 @@ fn challenge_b52_o00_b() -> Result<(), Box<dyn Error>> {
     assert_eq!(state.solution(), Some(Code::from_digits(3, 3, 4)?));
     Ok(())
 }
+
+#[test]
+fn challenge_b52_o00_b_from_facts() -> Result<(), Box<dyn Error>> {
+    let game = Game::new_from_verifier_numbers([12, 16, 18, 19, 21].iter().copied());
+    let guess = Code::from_digits(2, 1, 1)?;
+    let facts = [(0.into(), guess, Cross), (2.into(), guess, Check)];
+    assert_eq!(
+        game.codes_consistent_with(&facts),
+        Set::singleton(Code::from_digits(3, 3, 4)?)
+    );
+    // Only the first fact leaves more candidates.
+    assert!(game.codes_consistent_with(&facts[..1]).size() > 1);
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use std::process::{Command, Stdio};
+
+#[test]
+fn test_strategy_mode() {
+    let output = Command::new(env!("CARGO_BIN_EXE_turing-machine-ai"))
+        .args(["--strategy", "4", "9", "11", "14"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "code 111
+verifier A
+answer check
+  solution 221
+answer cross
+  solution 241
+In the worst case, the solution is found using 1 code, 1 verifier.
+"
+    );
+}
+
+#[test]
+fn test_strategy_mode_fewest_total() {
+    let output = Command::new(env!("CARGO_BIN_EXE_turing-machine-ai"))
+        .args(["--strategy", "--fewest-total", "4", "9", "11", "14"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("code 111\nverifier A\n"));
+    assert!(stdout.ends_with("using 1 code, 1 verifier.\n"));
+}
+
+#[test]
+fn test_invalid_verifier_number() {
+    let output = Command::new(env!("CARGO_BIN_EXE_turing-machine-ai"))
+        .args(["--strategy", "4", "9", "11", "49"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Usage"));
+}
+
+#[test]
+fn test_secret_answers_verifiers() {
+    for secret in ["221", "241"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_turing-machine-ai"))
+            .args(["--secret", secret, "4", "9", "11", "14"])
+            .stdin(Stdio::null())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(!stdout.contains("What does the verifier tell you?"));
+        assert!(stdout.contains("The verifier answers"));
+        assert!(stdout.ends_with(&format!("Solved! Solution: {secret}\n")));
+    }
+}
+
+#[test]
+fn test_secret_not_a_solution() {
+    let output = Command::new(env!("CARGO_BIN_EXE_turing-machine-ai"))
+        .args(["--secret", "111", "4", "9", "11", "14"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("111 is not a solution of this game"));
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "serde")]
+
+use std::error::Error;
+
+use proptest::prelude::*;
+
+use turing_machine_ai::{
+    code::{Code, Set},
+    game::{load_puzzles, Game, LoadError},
+    gametree::State,
+};
+
+#[test]
+fn test_round_trip() -> Result<(), Box<dyn Error>> {
+    let game = Game::new_from_verifier_numbers([3, 7, 10, 14].iter().copied());
+    let json = serde_json::to_string(&game)?;
+    assert_eq!(json, "[3,7,10,14]");
+    let deserialized: Game = serde_json::from_str(&json)?;
+    assert_eq!(deserialized, game);
+    Ok(())
+}
+
+#[test]
+fn test_out_of_range() {
+    assert!(serde_json::from_str::<Game>("[3,7,0,14]").is_err());
+    assert!(serde_json::from_str::<Game>("[3,7,49,14]").is_err());
+}
+
+#[test]
+fn test_strategy_tree() -> Result<(), Box<dyn Error>> {
+    let game = Game::new_from_verifier_numbers([4, 9, 11, 14].iter().copied());
+    let tree = State::new(&game).strategy_tree();
+    assert_eq!(
+        serde_json::to_value(&tree)?,
+        serde_json::json!({
+            "ChooseCode": {
+                "code": [1, 1, 1],
+                "child": {
+                    "ChooseVerifier": {
+                        "verifier": "A",
+                        "on_check": { "Solved": { "code": [2, 2, 1] } },
+                        "on_cross": { "Solved": { "code": [2, 4, 1] } }
+                    }
+                }
+            }
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_load_puzzles() -> Result<(), Box<dyn Error>> {
+    let json = r#"[
+        {"name": "first", "verifiers": [4, 9, 11, 14]},
+        {"name": "second", "verifiers": [3, 7, 10, 14]}
+    ]"#;
+    let puzzles = load_puzzles(json.as_bytes())?;
+    assert_eq!(
+        puzzles,
+        vec![
+            ("first".to_string(), "4 9 11 14".parse()?),
+            ("second".to_string(), "3 7 10 14".parse()?),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_load_invalid_puzzles() {
+    assert!(matches!(
+        load_puzzles(r#"[{"verifiers": [4, 9]}]"#.as_bytes()),
+        Err(LoadError::MissingName(0))
+    ));
+    assert!(matches!(
+        load_puzzles(r#"[{"name": "bad", "verifiers": [4, 49]}]"#.as_bytes()),
+        Err(LoadError::InvalidVerifiers { name, .. }) if name == "bad"
+    ));
+    assert!(matches!(
+        load_puzzles(r#"[{"name": "missing"}]"#.as_bytes()),
+        Err(LoadError::InvalidVerifiers { .. })
+    ));
+    assert!(matches!(
+        load_puzzles("not json".as_bytes()),
+        Err(LoadError::Json(_))
+    ));
+}
+
+#[test]
+fn test_code_round_trip() -> Result<(), Box<dyn Error>> {
+    let code = Code::from_digits(2, 4, 1)?;
+    let json = serde_json::to_string(&code)?;
+    assert_eq!(json, "[2,4,1]");
+    assert_eq!(serde_json::from_str::<Code>(&json)?, code);
+    assert!(serde_json::from_str::<Code>("[2,4,6]").is_err());
+    assert!(serde_json::from_str::<Code>("[2,4]").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_set_round_trip() -> Result<(), Box<dyn Error>> {
+    let set = Set::from_codes(&[
+        Code::from_digits(2, 4, 1)?,
+        Code::from_digits(1, 1, 1)?,
+        Code::from_digits(3, 5, 1)?,
+    ]);
+    let json = serde_json::to_string(&set)?;
+    assert_eq!(json, "[[1,1,1],[2,4,1],[3,5,1]]");
+    assert_eq!(serde_json::from_str::<Set>(&json)?, set);
+    assert!(serde_json::from_str::<Set>("[[0,1,1]]").is_err());
+    Ok(())
+}
+
+proptest! {
+    // Loading arbitrary input should give an error rather than panic.
+    #[test]
+    fn test_load_arbitrary_puzzles(input in any::<String>()) {
+        let _ = load_puzzles(input.as_bytes());
+    }
+
+    #[test]
+    fn test_load_arbitrary_verifiers(
+        numbers in proptest::collection::vec(any::<i64>(), 0..8)
+    ) {
+        let json = serde_json::json!([{ "name": "fuzz", "verifiers": numbers }]);
+        let _ = load_puzzles(json.to_string().as_bytes());
+    }
+}